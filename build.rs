@@ -109,5 +109,5 @@ fn main() {
     }
 
     let out_dir = env::var_os("OUT_DIR").unwrap();
-    fs::write(&Path::new(&out_dir).join("macros.rs"), &macros_rs).unwrap();
+    fs::write(Path::new(&out_dir).join("macros.rs"), &macros_rs).unwrap();
 }
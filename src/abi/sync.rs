@@ -29,10 +29,10 @@ pub const TWF_ANDW: MODE = 0x02;
  *  オブジェクトの状態の定義
  */
 /// スピンロックが取得されていない状態
-#[cfg(feature = "fmp3")]
+#[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
 pub const TSPN_UNL: STAT = 0x01;
 /// スピンロックが取得されている状態
-#[cfg(feature = "fmp3")]
+#[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
 pub const TSPN_LOC: STAT = 0x02;
 
 #[cfg(any(
@@ -12,6 +12,26 @@ pub type TMEHDR = Option<unsafe extern "C" fn(EXINF)>;
 /// 周期通知を動作状態で生成
 pub const TA_STA: ATR = 0x02;
 
+/*
+ *  オブジェクトの状態の定義
+ */
+/// 周期通知が動作していない状態
+pub const TCYC_STP: STAT = 0x01;
+/// 周期通知が動作している状態
+pub const TCYC_STA: STAT = 0x02;
+
+/// アラーム通知が動作していない状態
+pub const TALM_STP: STAT = 0x01;
+/// アラーム通知が動作している状態
+pub const TALM_STA: STAT = 0x02;
+
+/// オーバランハンドラが動作していない状態
+#[cfg(all(feature = "asp3", feature = "ovrhdr"))]
+pub const TOVR_STP: STAT = 0x01;
+/// オーバランハンドラが動作している状態
+#[cfg(all(feature = "asp3", feature = "ovrhdr"))]
+pub const TOVR_STA: STAT = 0x02;
+
 /*
  *  通知処理モードの定義
  */
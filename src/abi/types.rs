@@ -59,7 +59,7 @@ pub type SYSTIM = u64;
 /// 高分解能タイマのカウント値
 pub type HRTCNT = u64;
 
-#[cfg(all(feature = "asp3", feature = "ovrhdr"))]
+#[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
 /// プロセッサ時間［NGKI0573］
 pub type PRCTIM = u32;
 
@@ -0,0 +1,692 @@
+//! Alarm handlers
+use core::{fmt, marker::PhantomData, mem::MaybeUninit};
+
+use crate::{
+    abi,
+    error::{Error, ErrorCode, ErrorKind, Kind},
+    processor::Processor,
+    time::Duration,
+};
+
+define_error_kind! {
+    /// Error type for [`AlarmRef::start_after`].
+    pub enum StartAfterError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for StartAfterError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+// Note: `start_after_on` reduces to `start_after` on a uniprocessor kernel
+define_error_kind! {
+    /// Error type for [`AlarmRef::start_after_on`].
+    pub enum StartAfterOnError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The alarm handler cannot be assigned to the specified processor.
+        #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+        BadParam,
+    }
+}
+
+impl ErrorKind for StartAfterOnError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`AlarmRef::stop`].
+    pub enum StopError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for StopError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`AlarmRef::info`].
+    pub enum InfoError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for InfoError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`Alarm::build`].
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub enum BuildError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// Ran out of alarm handler IDs.
+        #[cfg(not(feature = "none"))]
+        OutOfMemory,
+        /// Bad parameter.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+#[cfg(feature = "dcre")]
+impl ErrorKind for BuildError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            // `E_MACV` is considered critical, hence excluded
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOID => Some(Self::OutOfMemory(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR | abi::E_RSATR => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`AlarmRef::delete`].
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub enum DeleteError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+#[cfg(feature = "dcre")]
+impl ErrorKind for DeleteError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+/// Alarm handler information returned by [`AlarmRef::info`].
+#[derive(Debug, Clone, Copy)]
+pub struct Info {
+    #[cfg(not(feature = "none"))]
+    raw: abi::T_RALM,
+}
+
+impl Info {
+    /// Get a flag indicating whether the alarm handler is in the operational
+    /// state.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => self.raw.almstat == abi::TALM_STA,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the remaining time until the invocation. Returns `None` if
+    /// the alarm handler is not in the operational state.
+    #[inline]
+    pub fn time_left(&self) -> Option<Duration> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => {
+                if self.is_active() {
+                    // Safety: The kernel returns a valid duration value
+                    Some(unsafe { Duration::from_raw(self.raw.lefttim) })
+                } else {
+                    None
+                }
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the processor to which the alarm handler is assigned.
+    #[inline]
+    pub fn processor(&self) -> Processor {
+        match () {
+            #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+            () => Processor::from_raw(self.raw.prcid).unwrap(),
+            #[cfg(not(any(feature = "none", feature = "fmp3", feature = "solid_fmp3")))]
+            () => Processor::UNIPROCESSOR,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// A borrowed reference to an alarm handler.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct AlarmRef<'a> {
+    id: abi::NonNullID,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl fmt::Debug for AlarmRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Alarm({})", self.id)
+    }
+}
+
+/// # Object ID conversion
+impl AlarmRef<'_> {
+    /// Construct a `AlarmRef` from a raw object ID.
+    ///
+    /// # Safety
+    ///
+    /// See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
+        Self {
+            id,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get the raw object ID.
+    #[inline]
+    pub const fn as_raw(self) -> abi::ID {
+        self.id.get()
+    }
+
+    /// Get the raw object ID as [` abi::NonNullID`].
+    #[inline]
+    pub const fn as_raw_nonnull(self) -> abi::NonNullID {
+        self.id
+    }
+}
+
+/// # Management
+impl AlarmRef<'_> {
+    /// `del_alm`: Delete the alarm handler.
+    ///
+    /// # Safety
+    ///
+    /// See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    #[doc(alias = "del_alm")]
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub unsafe fn delete(self) -> Result<(), Error<DeleteError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::del_alm(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `ref_alm`: Get the alarm handler's general information.
+    #[inline]
+    #[doc(alias = "ref_alm")]
+    pub fn info(self) -> Result<Info, Error<InfoError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut pri = MaybeUninit::uninit();
+                Error::err_if_negative(abi::ref_alm(self.as_raw(), pri.as_mut_ptr()))?;
+                Ok(Info {
+                    raw: pri.assume_init(),
+                })
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// # Alarm Handler Operations
+impl AlarmRef<'_> {
+    /// `sta_alm`: Start the alarm handler, making it fire after the
+    /// specified duration. Restarts it if it's already in the operational
+    /// state.
+    #[inline]
+    #[doc(alias = "sta_alm")]
+    pub fn start_after(self, delay: Duration) -> Result<(), Error<StartAfterError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::sta_alm(self.as_raw(), delay.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `msta_alm`: Start the alarm handler, making it fire after the
+    /// specified duration on the specified processor. Restarts it if it's
+    /// already in the operational state.
+    #[inline]
+    #[doc(alias = "msta_alm")]
+    pub fn start_after_on(
+        self,
+        delay: Duration,
+        processor: Processor,
+    ) -> Result<(), Error<StartAfterOnError>> {
+        match () {
+            #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+            () => unsafe {
+                Error::err_if_negative(abi::msta_alm(
+                    self.as_raw(),
+                    delay.as_raw(),
+                    processor.as_raw(),
+                ))?;
+                Ok(())
+            },
+            #[cfg(not(any(feature = "none", feature = "fmp3", feature = "solid_fmp3")))]
+            () => {
+                let Processor::UNIPROCESSOR = processor;
+                self.start_after(delay)
+                    .map_err(|e| unsafe { Error::new_unchecked(e.code()) })
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `stp_alm`: Stop the alarm handler.
+    #[inline]
+    #[doc(alias = "stp_alm")]
+    pub fn stop(self) -> Result<(), Error<StopError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::stp_alm(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+#[cfg(feature = "dcre")]
+pub use self::owned::*;
+
+#[cfg(feature = "dcre")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+mod owned {
+    use super::*;
+    #[cfg(any(
+        all(feature = "solid_fmp3", feature = "systim_local"),
+        feature = "none"
+    ))]
+    use crate::task::IntoProcessorSet;
+    use crate::{
+        task::TaskRef,
+        time::{ErrorNotify, Notify},
+    };
+
+    /// The builder type for [alarm handlers](Alarm). Created by
+    /// [`Alarm::build`].
+    ///
+    /// Its generic parameters are an implementation detail.
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    #[must_use = "`Builder` creates nothing unless you call `.finish()`"]
+    pub struct Builder<Start> {
+        #[allow(dead_code)]
+        start: Start,
+        #[allow(dead_code)]
+        assign_to_current_procesor: bool,
+        #[cfg(not(feature = "none"))]
+        raw: abi::T_CALM,
+    }
+
+    /// Builder field hole types
+    #[allow(non_camel_case_types)]
+    #[doc(hidden)]
+    pub mod builder_hole {
+        pub struct __start_is_not_specified__;
+    }
+
+    impl Alarm {
+        /// `acre_alm`: Create a builder for `Alarm`.
+        ///
+        /// # Example
+        ///
+        /// ```rust,no_run
+        /// use itron::{alarm::Alarm, time::Duration};
+        /// let captured_variable = 42u16;
+        /// let alarm = Alarm::build()
+        ///     .start(move || { let _ = captured_variable; })
+        ///     .finish()
+        ///     .expect("failed to create an alarm handler");
+        ///
+        /// alarm.as_ref().start_after(Duration::from_millis(10).unwrap())
+        ///     .expect("failed to start the alarm handler");
+        /// assert!(alarm.as_ref().info().unwrap().is_active());
+        /// alarm.as_ref().stop().expect("failed to stop the alarm handler");
+        /// ```
+        ///
+        /// Wake up a task on timeout:
+        ///
+        /// ```rust,no_run
+        /// use itron::{alarm::Alarm, task, time::Duration};
+        /// let current_task = task::current().unwrap();
+        /// // Safety: The current task outlives the alarm handler
+        /// let current_task =
+        ///     unsafe { task::TaskRef::from_raw_nonnull(current_task.as_raw_nonnull()) };
+        /// let alarm = Alarm::build()
+        ///     .wake_task(current_task)
+        ///     .finish()
+        ///     .expect("failed to create an alarm handler");
+        ///
+        /// alarm.as_ref().start_after(Duration::from_millis(10).unwrap())
+        ///     .expect("failed to start the alarm handler");
+        /// task::sleep().expect("failed to sleep");
+        /// ```
+        #[inline]
+        #[doc(alias = "acre_alm")]
+        pub fn build() -> Builder<builder_hole::__start_is_not_specified__> {
+            Builder {
+                start: builder_hole::__start_is_not_specified__,
+                assign_to_current_procesor: true,
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CALM {
+                    almatr: abi::TA_NULL,
+                    nfyinfo: abi::T_NFYINFO {
+                        nfymode: abi::TNFY_HANDLER,
+                        nfy: abi::T_NFY {
+                            handler: abi::T_NFY_HDR {
+                                exinf: abi::EXINF::uninit(),
+                                tmehdr: None,
+                            },
+                        },
+                        enfy: abi::T_ENFY {
+                            setvar: abi::T_ENFY_VAR {
+                                p_var: core::ptr::null_mut(),
+                            },
+                        },
+                    },
+                    #[cfg(all(feature = "solid_fmp3", feature = "systim_local"))]
+                    iprcid: 0,
+                    #[cfg(all(feature = "solid_fmp3", feature = "systim_local"))]
+                    affinity: abi::uint_t::MAX,
+                },
+            }
+        }
+    }
+
+    impl<Start> Builder<Start> {
+        /// (**Mandatory**) Specify the entry point. Equivalent to
+        /// `self.notify(Notify::handler(value))`.
+        #[inline]
+        pub fn start(self, value: impl crate::closure::IntoClosure + Send) -> Builder<()> {
+            self.notify(Notify::handler(value))
+        }
+
+        /// (**Mandatory**) Specify to wake up the specified task instead of
+        /// calling a handler function. Equivalent to
+        /// `self.notify(Notify::WakeTask(task))`.
+        #[inline]
+        #[doc(alias = "TNFY_WUPTSK")]
+        pub fn wake_task(self, task: TaskRef<'static>) -> Builder<()> {
+            self.notify(Notify::WakeTask(task))
+        }
+
+        /// (**Mandatory**) Specify the notification method.
+        #[inline]
+        pub fn notify(self, value: Notify) -> Builder<()> {
+            #[cfg(not(feature = "none"))]
+            let mut raw = self.raw;
+            #[cfg(not(feature = "none"))]
+            value.write_to(&mut raw.nfyinfo);
+            #[cfg(feature = "none")]
+            let _ = value;
+            Builder {
+                start: (),
+                assign_to_current_procesor: self.assign_to_current_procesor,
+                #[cfg(not(feature = "none"))]
+                raw,
+            }
+        }
+
+        /// Specify the error notification method. Defaults to no error
+        /// notification when unspecified.
+        ///
+        /// This can't be used with [`Notify::Handler`].
+        #[inline]
+        pub fn error_notify(self, value: ErrorNotify) -> Self {
+            #[cfg(not(feature = "none"))]
+            let mut raw = self.raw;
+            #[cfg(not(feature = "none"))]
+            value.write_to(&mut raw.nfyinfo);
+            #[cfg(feature = "none")]
+            let _ = value;
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw,
+                ..self
+            }
+        }
+
+        /// Specify the alarm handler's initial assigned processor. Defaults
+        /// to the current processor when unspecified.
+        #[inline]
+        #[cfg(any(
+            all(feature = "solid_fmp3", feature = "systim_local"),
+            feature = "none"
+        ))]
+        #[cfg_attr(
+            feature = "doc_cfg",
+            doc(cfg(all(feature = "solid_fmp3", feature = "systim_local")))
+        )]
+        pub fn initial_processor(self, value: Processor) -> Self {
+            #[cfg(not(feature = "solid_fmp3"))]
+            let _ = value;
+            Builder {
+                assign_to_current_procesor: false,
+                #[cfg(feature = "solid_fmp3")]
+                raw: abi::T_CALM {
+                    iprcid: value.as_raw(),
+                    ..self.raw
+                },
+                ..self
+            }
+        }
+
+        /// Specify the alarm handler's assignable processsor set. Defaults
+        /// to all processors when unspecified.
+        ///
+        /// This function might panic if an invalid processor ID is specified
+        /// or the set is empty.
+        #[inline]
+        #[cfg(any(
+            all(feature = "solid_fmp3", feature = "systim_local"),
+            feature = "none"
+        ))]
+        #[cfg_attr(
+            feature = "doc_cfg",
+            doc(cfg(all(feature = "solid_fmp3", feature = "systim_local")))
+        )]
+        pub fn processor_affinity(self, value: impl IntoProcessorSet) -> Self {
+            #[cfg(not(feature = "solid_fmp3"))]
+            value.assert_non_empty();
+
+            Builder {
+                #[cfg(feature = "solid_fmp3")]
+                raw: abi::T_CALM {
+                    affinity: value.into_uint_t(),
+                    ..self.raw
+                },
+                ..self
+            }
+        }
+    }
+
+    impl Builder<()> {
+        /// Create an alarm handler using the specified parameters.
+        #[allow(unused_mut)]
+        pub fn finish(mut self) -> Result<Alarm, Error<BuildError>> {
+            #[cfg(all(feature = "solid_fmp3", feature = "systim_local"))]
+            if self.assign_to_current_procesor {
+                unsafe { Error::err_if_negative(abi::get_pid(&mut self.raw.iprcid))? };
+            }
+
+            match () {
+                #[cfg(not(feature = "none"))]
+                () => unsafe {
+                    let id = Error::err_if_negative(abi::acre_alm(&self.raw))?;
+                    // Safety: We own the alarm handler we create
+                    Ok(Alarm::from_raw_nonnull(abi::NonNullID::new_unchecked(id)))
+                },
+                #[cfg(feature = "none")]
+                () => unimplemented!(),
+            }
+        }
+    }
+
+    /// An owned alarm handler.
+    ///
+    /// [Deletes] the alarm handler automatically when dropped. The destructor
+    /// will panic if the deletion fails.
+    ///
+    /// [Deletes]: AlarmRef::delete
+    #[derive(PartialEq, Eq)]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub struct Alarm(AlarmRef<'static>);
+
+    impl fmt::Debug for Alarm {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    impl Drop for Alarm {
+        #[inline]
+        fn drop(&mut self) {
+            unsafe { self.0.delete().unwrap() };
+        }
+    }
+
+    impl Alarm {
+        /// Construct a `Alarm` from a raw object ID.
+        ///
+        /// # Safety
+        ///
+        /// See [Object ID Wrappers](crate#object-id-wrappers).
+        #[inline]
+        pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
+            Self(unsafe { AlarmRef::from_raw_nonnull(id) })
+        }
+
+        /// Consume and "leak" `self`, returning a reference `AlarmRef<'a>`.
+        #[inline]
+        pub const fn leak<'a>(self) -> AlarmRef<'a> {
+            let out = self.0;
+            core::mem::forget(self);
+            out
+        }
+
+        /// Get the raw object ID.
+        #[inline]
+        pub const fn as_raw(&self) -> abi::ID {
+            self.0.as_raw()
+        }
+
+        /// Get the raw object ID as [` abi::NonNullID`].
+        #[inline]
+        pub const fn as_raw_nonnull(&self) -> abi::NonNullID {
+            self.0.as_raw_nonnull()
+        }
+
+        /// Borrow `Alarm` as [`AlarmRef`].
+        ///
+        /// Use this to perform operations on alarm handlers because most of
+        /// the methods are implemented on `AlarmRef` but not `Alarm`.
+        #[inline]
+        pub const fn as_ref(&self) -> AlarmRef<'_> {
+            self.0
+        }
+    }
+}
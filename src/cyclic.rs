@@ -0,0 +1,705 @@
+//! Cyclic handlers
+use core::{fmt, marker::PhantomData, mem::MaybeUninit};
+
+use crate::{
+    abi,
+    error::{Error, ErrorCode, ErrorKind, Kind},
+    processor::Processor,
+    time::Duration,
+};
+
+define_error_kind! {
+    /// Error type for [`CyclicRef::start`].
+    pub enum StartError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for StartError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+// Note: `start_on` reduces to `start` on a uniprocessor kernel
+define_error_kind! {
+    /// Error type for [`CyclicRef::start_on`].
+    pub enum StartOnError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The cyclic handler cannot be assigned to the specified processor.
+        #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+        BadParam,
+    }
+}
+
+impl ErrorKind for StartOnError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`CyclicRef::stop`].
+    pub enum StopError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for StopError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`CyclicRef::info`].
+    pub enum InfoError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for InfoError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`Cyclic::build`].
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub enum BuildError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// Ran out of cyclic handler IDs.
+        #[cfg(not(feature = "none"))]
+        OutOfMemory,
+        /// Bad parameter, e.g., the period is zero or the phase is longer
+        /// than the period.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+#[cfg(feature = "dcre")]
+impl ErrorKind for BuildError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            // `E_MACV` is considered critical, hence excluded
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOID => Some(Self::OutOfMemory(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR | abi::E_RSATR => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`CyclicRef::delete`].
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub enum DeleteError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+#[cfg(feature = "dcre")]
+impl ErrorKind for DeleteError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+/// Cyclic handler information returned by [`CyclicRef::info`].
+#[derive(Debug, Clone, Copy)]
+pub struct Info {
+    #[cfg(not(feature = "none"))]
+    raw: abi::T_RCYC,
+}
+
+impl Info {
+    /// Get a flag indicating whether the cyclic handler is in the operational
+    /// state.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => self.raw.cycstat == abi::TCYC_STA,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the remaining time until the next invocation. Returns `None` if
+    /// the cyclic handler is not in the operational state.
+    #[inline]
+    pub fn time_left(&self) -> Option<Duration> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => {
+                if self.is_active() {
+                    // Safety: The kernel returns a valid duration value
+                    Some(unsafe { Duration::from_raw(self.raw.lefttim) })
+                } else {
+                    None
+                }
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the processor to which the cyclic handler is assigned.
+    #[inline]
+    pub fn processor(&self) -> Processor {
+        match () {
+            #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+            () => Processor::from_raw(self.raw.prcid).unwrap(),
+            #[cfg(not(any(feature = "none", feature = "fmp3", feature = "solid_fmp3")))]
+            () => Processor::UNIPROCESSOR,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// A borrowed reference to a cyclic handler.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct CyclicRef<'a> {
+    id: abi::NonNullID,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl fmt::Debug for CyclicRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Cyclic({})", self.id)
+    }
+}
+
+/// # Object ID conversion
+impl CyclicRef<'_> {
+    /// Construct a `CyclicRef` from a raw object ID.
+    ///
+    /// # Safety
+    ///
+    /// See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
+        Self {
+            id,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get the raw object ID.
+    #[inline]
+    pub const fn as_raw(self) -> abi::ID {
+        self.id.get()
+    }
+
+    /// Get the raw object ID as [` abi::NonNullID`].
+    #[inline]
+    pub const fn as_raw_nonnull(self) -> abi::NonNullID {
+        self.id
+    }
+}
+
+/// # Management
+impl CyclicRef<'_> {
+    /// `del_cyc`: Delete the cyclic handler.
+    ///
+    /// # Safety
+    ///
+    /// See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    #[doc(alias = "del_cyc")]
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub unsafe fn delete(self) -> Result<(), Error<DeleteError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::del_cyc(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `ref_cyc`: Get the cyclic handler's general information.
+    #[inline]
+    #[doc(alias = "ref_cyc")]
+    pub fn info(self) -> Result<Info, Error<InfoError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut pri = MaybeUninit::uninit();
+                Error::err_if_negative(abi::ref_cyc(self.as_raw(), pri.as_mut_ptr()))?;
+                Ok(Info {
+                    raw: pri.assume_init(),
+                })
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// # Cyclic Handler Operations
+impl CyclicRef<'_> {
+    /// `sta_cyc`: Start the cyclic handler. Restarts it if it's already in
+    /// the operational state.
+    #[inline]
+    #[doc(alias = "sta_cyc")]
+    pub fn start(self) -> Result<(), Error<StartError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::sta_cyc(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `msta_cyc`: Start the cyclic handler, assigning it to the specified
+    /// processor. Restarts it if it's already in the operational state.
+    #[inline]
+    #[doc(alias = "msta_cyc")]
+    pub fn start_on(self, processor: Processor) -> Result<(), Error<StartOnError>> {
+        match () {
+            #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+            () => unsafe {
+                Error::err_if_negative(abi::msta_cyc(self.as_raw(), processor.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(not(any(feature = "none", feature = "fmp3", feature = "solid_fmp3")))]
+            () => {
+                let Processor::UNIPROCESSOR = processor;
+                self.start()
+                    .map_err(|e| unsafe { Error::new_unchecked(e.code()) })
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `stp_cyc`: Stop the cyclic handler.
+    #[inline]
+    #[doc(alias = "stp_cyc")]
+    pub fn stop(self) -> Result<(), Error<StopError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::stp_cyc(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+#[cfg(feature = "dcre")]
+pub use self::owned::*;
+
+#[cfg(feature = "dcre")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+mod owned {
+    use super::*;
+    #[cfg(any(
+        all(feature = "solid_fmp3", feature = "systim_local"),
+        feature = "none"
+    ))]
+    use crate::task::IntoProcessorSet;
+    use crate::time::{ErrorNotify, Notify};
+
+    /// The builder type for [cyclic handlers](Cyclic). Created by
+    /// [`Cyclic::build`].
+    ///
+    /// Its generic parameters are an implementation detail.
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    #[must_use = "`Builder` creates nothing unless you call `.finish()`"]
+    pub struct Builder<Start, Period> {
+        #[allow(dead_code)]
+        start: Start,
+        #[allow(dead_code)]
+        period: Period,
+        #[allow(dead_code)]
+        assign_to_current_procesor: bool,
+        #[cfg(not(feature = "none"))]
+        raw: abi::T_CCYC,
+    }
+
+    /// Builder field hole types
+    #[allow(non_camel_case_types)]
+    #[doc(hidden)]
+    pub mod builder_hole {
+        pub struct __start_is_not_specified__;
+        pub struct __period_is_not_specified__;
+    }
+
+    impl Cyclic {
+        /// `acre_cyc`: Create a builder for `Cyclic`.
+        ///
+        /// # Example
+        ///
+        /// ```rust,no_run
+        /// use itron::{cyclic::Cyclic, time::Duration};
+        /// let captured_variable = 42u16;
+        /// let cyclic = Cyclic::build()
+        ///     .start(move || { let _ = captured_variable; })
+        ///     .period(Duration::from_millis(10).unwrap())
+        ///     .phase(Duration::from_millis(5).unwrap())
+        ///     .finish_and_start()
+        ///     .expect("failed to create a cyclic handler");
+        ///
+        /// assert!(cyclic.as_ref().info().unwrap().is_active());
+        /// cyclic.as_ref().stop().expect("failed to stop the cyclic handler");
+        /// ```
+        #[inline]
+        #[doc(alias = "acre_cyc")]
+        pub fn build() -> Builder<
+            builder_hole::__start_is_not_specified__,
+            builder_hole::__period_is_not_specified__,
+        > {
+            Builder {
+                start: builder_hole::__start_is_not_specified__,
+                period: builder_hole::__period_is_not_specified__,
+                assign_to_current_procesor: true,
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CCYC {
+                    cycatr: abi::TA_NULL,
+                    nfyinfo: abi::T_NFYINFO {
+                        nfymode: abi::TNFY_HANDLER,
+                        nfy: abi::T_NFY {
+                            handler: abi::T_NFY_HDR {
+                                exinf: abi::EXINF::uninit(),
+                                tmehdr: None,
+                            },
+                        },
+                        enfy: abi::T_ENFY {
+                            setvar: abi::T_ENFY_VAR {
+                                p_var: core::ptr::null_mut(),
+                            },
+                        },
+                    },
+                    cyctim: 0,
+                    cycphs: 0,
+                    #[cfg(all(feature = "solid_fmp3", feature = "systim_local"))]
+                    iprcid: 0,
+                    #[cfg(all(feature = "solid_fmp3", feature = "systim_local"))]
+                    affinity: abi::uint_t::MAX,
+                },
+            }
+        }
+    }
+
+    impl<Start, Period> Builder<Start, Period> {
+        /// (**Mandatory**) Specify the entry point. Equivalent to
+        /// `self.notify(Notify::handler(value))`.
+        #[inline]
+        pub fn start(self, value: impl crate::closure::IntoClosure + Send) -> Builder<(), Period> {
+            self.notify(Notify::handler(value))
+        }
+
+        /// (**Mandatory**) Specify the notification method.
+        #[inline]
+        pub fn notify(self, value: Notify) -> Builder<(), Period> {
+            #[cfg(not(feature = "none"))]
+            let mut raw = self.raw;
+            #[cfg(not(feature = "none"))]
+            value.write_to(&mut raw.nfyinfo);
+            #[cfg(feature = "none")]
+            let _ = value;
+            Builder {
+                start: (),
+                period: self.period,
+                assign_to_current_procesor: self.assign_to_current_procesor,
+                #[cfg(not(feature = "none"))]
+                raw,
+            }
+        }
+
+        /// Specify the error notification method. Defaults to no error
+        /// notification when unspecified.
+        ///
+        /// This can't be used with [`Notify::Handler`].
+        #[inline]
+        pub fn error_notify(self, value: ErrorNotify) -> Self {
+            #[cfg(not(feature = "none"))]
+            let mut raw = self.raw;
+            #[cfg(not(feature = "none"))]
+            value.write_to(&mut raw.nfyinfo);
+            #[cfg(feature = "none")]
+            let _ = value;
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw,
+                ..self
+            }
+        }
+
+        /// (**Mandatory**) Specify the invocation period.
+        #[inline]
+        pub fn period(self, value: Duration) -> Builder<Start, ()> {
+            Builder {
+                start: self.start,
+                period: (),
+                assign_to_current_procesor: self.assign_to_current_procesor,
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CCYC {
+                    cyctim: value.as_raw(),
+                    ..self.raw
+                },
+            }
+        }
+
+        /// Specify the invocation phase, i.e., the delay before the first
+        /// invocation after the creation. Defaults to zero when unspecified.
+        #[inline]
+        pub fn phase(self, value: Duration) -> Self {
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CCYC {
+                    cycphs: value.as_raw(),
+                    ..self.raw
+                },
+                ..self
+            }
+        }
+
+        /// Specify the cyclic handler's initial assigned processor. Defaults
+        /// to the current processor when unspecified.
+        #[inline]
+        #[cfg(any(
+            all(feature = "solid_fmp3", feature = "systim_local"),
+            feature = "none"
+        ))]
+        #[cfg_attr(
+            feature = "doc_cfg",
+            doc(cfg(all(feature = "solid_fmp3", feature = "systim_local")))
+        )]
+        pub fn initial_processor(self, value: Processor) -> Self {
+            #[cfg(not(feature = "solid_fmp3"))]
+            let _ = value;
+            Builder {
+                assign_to_current_procesor: false,
+                #[cfg(feature = "solid_fmp3")]
+                raw: abi::T_CCYC {
+                    iprcid: value.as_raw(),
+                    ..self.raw
+                },
+                ..self
+            }
+        }
+
+        /// Specify the cyclic handler's assignable processsor set. Defaults
+        /// to all processors when unspecified.
+        ///
+        /// This function might panic if an invalid processor ID is specified
+        /// or the set is empty.
+        #[inline]
+        #[cfg(any(
+            all(feature = "solid_fmp3", feature = "systim_local"),
+            feature = "none"
+        ))]
+        #[cfg_attr(
+            feature = "doc_cfg",
+            doc(cfg(all(feature = "solid_fmp3", feature = "systim_local")))
+        )]
+        pub fn processor_affinity(self, value: impl IntoProcessorSet) -> Self {
+            #[cfg(not(feature = "solid_fmp3"))]
+            value.assert_non_empty();
+
+            Builder {
+                #[cfg(feature = "solid_fmp3")]
+                raw: abi::T_CCYC {
+                    affinity: value.into_uint_t(),
+                    ..self.raw
+                },
+                ..self
+            }
+        }
+    }
+
+    impl Builder<(), ()> {
+        /// Create a cyclic handler using the specified parameters.
+        #[allow(unused_mut)]
+        pub fn finish(mut self) -> Result<Cyclic, Error<BuildError>> {
+            #[cfg(all(feature = "solid_fmp3", feature = "systim_local"))]
+            if self.assign_to_current_procesor {
+                unsafe { Error::err_if_negative(abi::get_pid(&mut self.raw.iprcid))? };
+            }
+
+            match () {
+                #[cfg(not(feature = "none"))]
+                () => unsafe {
+                    let id = Error::err_if_negative(abi::acre_cyc(&self.raw))?;
+                    // Safety: We own the cyclic handler we create
+                    Ok(Cyclic::from_raw_nonnull(abi::NonNullID::new_unchecked(id)))
+                },
+                #[cfg(feature = "none")]
+                () => unimplemented!(),
+            }
+        }
+
+        /// Create a cyclic handler in the operational state using the
+        /// specified parameters.
+        #[allow(unused_mut)]
+        #[doc(alias = "TA_STA")]
+        pub fn finish_and_start(mut self) -> Result<Cyclic, Error<BuildError>> {
+            #[cfg(not(feature = "none"))]
+            {
+                self.raw.cycatr |= abi::TA_STA;
+            }
+
+            self.finish()
+        }
+    }
+
+    /// An owned cyclic handler.
+    ///
+    /// [Deletes] the cyclic handler automatically when dropped. The destructor
+    /// will panic if the deletion fails.
+    ///
+    /// [Deletes]: CyclicRef::delete
+    #[derive(PartialEq, Eq)]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub struct Cyclic(CyclicRef<'static>);
+
+    impl fmt::Debug for Cyclic {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    impl Drop for Cyclic {
+        #[inline]
+        fn drop(&mut self) {
+            unsafe { self.0.delete().unwrap() };
+        }
+    }
+
+    impl Cyclic {
+        /// Construct a `Cyclic` from a raw object ID.
+        ///
+        /// # Safety
+        ///
+        /// See [Object ID Wrappers](crate#object-id-wrappers).
+        #[inline]
+        pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
+            Self(unsafe { CyclicRef::from_raw_nonnull(id) })
+        }
+
+        /// Consume and "leak" `self`, returning a reference `CyclicRef<'a>`.
+        #[inline]
+        pub const fn leak<'a>(self) -> CyclicRef<'a> {
+            let out = self.0;
+            core::mem::forget(self);
+            out
+        }
+
+        /// Get the raw object ID.
+        #[inline]
+        pub const fn as_raw(&self) -> abi::ID {
+            self.0.as_raw()
+        }
+
+        /// Get the raw object ID as [` abi::NonNullID`].
+        #[inline]
+        pub const fn as_raw_nonnull(&self) -> abi::NonNullID {
+            self.0.as_raw_nonnull()
+        }
+
+        /// Borrow `Cyclic` as [`CyclicRef`].
+        ///
+        /// Use this to perform operations on cyclic handlers because most of
+        /// the methods are implemented on `CyclicRef` but not `Cyclic`.
+        #[inline]
+        pub const fn as_ref(&self) -> CyclicRef<'_> {
+            self.0
+        }
+    }
+}
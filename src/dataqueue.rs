@@ -759,11 +759,56 @@ mod owned {
         }
     }
 
-    // TODO: abi::T_CDTQ::dtqmb
+    /// A statically allocated storage for data elements (`T_CDTQ::dtqmb`)
+    /// used by a dataqueue created by [`Builder::storage`]. `N` is the
+    /// dataqueue's capacity, measured in number of data elements.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use itron::dataqueue::{Dataqueue, DataqueueStorage};
+    /// static mut STORAGE: DataqueueStorage<4> = DataqueueStorage::new();
+    /// let dataqueue = Dataqueue::build()
+    ///     .storage(unsafe { &mut *core::ptr::addr_of_mut!(STORAGE) })
+    ///     .finish()
+    ///     .expect("failed to create a dataqueue");
+    /// ```
+    #[repr(transparent)]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub struct DataqueueStorage<const N: usize> {
+        /// `DTQMB` consists of a single `intptr_t`.
+        region: MaybeUninit<[isize; N]>,
+    }
+
+    impl<const N: usize> DataqueueStorage<N> {
+        /// Construct a `DataqueueStorage`.
+        #[inline]
+        pub const fn new() -> Self {
+            Self {
+                region: MaybeUninit::uninit(),
+            }
+        }
+    }
+
+    impl<const N: usize> Default for DataqueueStorage<N> {
+        #[inline]
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<const N: usize> fmt::Debug for DataqueueStorage<N> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("DataqueueStorage")
+                .field("capacity", &N)
+                .finish()
+        }
+    }
 
     impl<Capacity> Builder<Capacity> {
         /// (**Mandatory**) Specify the dataqueue's capacity, measured in
-        /// number of data elements.
+        /// number of data elements. The kernel allocates storage for data
+        /// elements.
         #[inline]
         pub fn capacity(self, value: usize) -> Builder<()> {
             let (capacity, capacity_overflow) = match value.try_into() {
@@ -778,11 +823,30 @@ mod owned {
                 #[cfg(not(feature = "none"))]
                 raw: abi::T_CDTQ {
                     dtqcnt: capacity,
+                    dtqmb: core::ptr::null_mut(),
                     ..self.raw
                 },
             }
         }
 
+        /// (**Mandatory**) Specify to use the specified storage for data
+        /// elements. This also sets the dataqueue's capacity to `N`.
+        #[inline]
+        pub fn storage<const N: usize>(
+            self,
+            storage: &'static mut DataqueueStorage<N>,
+        ) -> Builder<()> {
+            let builder = self.capacity(N);
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CDTQ {
+                    dtqmb: storage.region.as_mut_ptr() as *mut u8,
+                    ..builder.raw
+                },
+                ..builder
+            }
+        }
+
         /// Specify the queue order. Defaults to `Fifo` when unspecified.
         #[inline]
         pub fn queue_order(self, value: QueueOrder) -> Self {
@@ -0,0 +1,202 @@
+//! Diagnostics
+use core::mem::MaybeUninit;
+
+use crate::{
+    abi,
+    error::{Error, ErrorCode, ErrorKind, Kind},
+    mutex::MutexRef,
+    task::{TaskRef, WaitCause},
+};
+
+define_error_kind! {
+    /// Error type for [`find_deadlocks`].
+    pub enum FindDeadlocksError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for FindDeadlocksError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+/// An edge of a wait-for graph found by [`find_deadlocks`]: the task
+/// [`task_id`](Self::task_id) is waiting to lock the mutex
+/// [`mutex_id`](Self::mutex_id), which is owned by the next edge's task.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct DeadlockEdge {
+    task_id: abi::NonNullID,
+    mutex_id: abi::NonNullID,
+}
+
+impl DeadlockEdge {
+    /// Get the waiting task's ID.
+    #[inline]
+    pub fn task_id(&self) -> abi::NonNullID {
+        self.task_id
+    }
+
+    /// Get the ID of the mutex the task is waiting for.
+    #[inline]
+    pub fn mutex_id(&self) -> abi::NonNullID {
+        self.mutex_id
+    }
+
+    /// Get the waiting task.
+    ///
+    /// # Safety
+    ///
+    /// The returned `TaskRef` is constructed from a task ID found in the
+    /// kernel state. See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    pub unsafe fn task<'a>(&self) -> TaskRef<'a> {
+        unsafe { TaskRef::from_raw_nonnull(self.task_id) }
+    }
+
+    /// Get the mutex the task is waiting for.
+    ///
+    /// # Safety
+    ///
+    /// The returned `MutexRef` is constructed from a mutex ID found in the
+    /// kernel state. See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    pub unsafe fn mutex<'a>(&self) -> MutexRef<'a> {
+        unsafe { MutexRef::from_raw_nonnull(self.mutex_id) }
+    }
+}
+
+/// Find tasks that are deadlocked on mutexes.
+///
+/// This function builds the wait-for graph between tasks and mutexes from
+/// the tasks' wait causes ([`WaitCause::Mutex`]) and the mutexes' owners,
+/// and calls `on_deadlock` for each cycle in the graph. Each cycle is
+/// reported once as a list of edges, starting from the task with the lowest
+/// ID.
+///
+/// `buf` is used as a scratch space for following the wait-for chains. A
+/// cycle longer than `buf` isn't reported. A buffer with as many elements as
+/// there are tasks is always large enough.
+///
+/// The kernel state is inspected while the system keeps running, so the
+/// result is only reliable for deadlocks, which don't resolve by themselves.
+/// This function is intended to be called periodically from a low-priority
+/// task.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use core::mem::MaybeUninit;
+/// use itron::diagnostics::{find_deadlocks, DeadlockEdge};
+/// let mut buf = [MaybeUninit::<DeadlockEdge>::uninit(); 16];
+/// find_deadlocks(&mut buf, |cycle| {
+///     for edge in cycle {
+///         // The task `edge.task_id()` is waiting for
+///         // the mutex `edge.mutex_id()`
+///
+///         // Safety: We only query the mutex's state
+///         let mutex = unsafe { edge.mutex() };
+///         println!("{:?}: {:?}", mutex, mutex.info());
+///     }
+/// })
+/// .expect("failed to inspect the kernel state");
+/// ```
+pub fn find_deadlocks(
+    buf: &mut [MaybeUninit<DeadlockEdge>],
+    mut on_deadlock: impl FnMut(&[DeadlockEdge]),
+) -> Result<(), Error<FindDeadlocksError>> {
+    let mut start = abi::NonNullID::new(1).unwrap();
+    loop {
+        if !follow_chain(start, buf, &mut on_deadlock)? {
+            return Ok(());
+        }
+
+        start = match start.get().checked_add(1).and_then(abi::NonNullID::new) {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+    }
+}
+
+/// Follow the wait-for chain starting from the task `start` and report it
+/// if it's a cycle in which `start` has the lowest task ID. Returns `false`
+/// if `start` is past the last task ID.
+fn follow_chain(
+    start: abi::NonNullID,
+    buf: &mut [MaybeUninit<DeadlockEdge>],
+    on_deadlock: &mut impl FnMut(&[DeadlockEdge]),
+) -> Result<bool, Error<FindDeadlocksError>> {
+    let mut len = 0;
+    let mut task_id = start;
+    loop {
+        // Safety: We only query the task's state
+        let task_info = match unsafe { TaskRef::from_raw_nonnull(task_id) }.info() {
+            Ok(info) => info,
+            Err(e) if task_id == start && e.code().get() == abi::E_ID => return Ok(false),
+            Err(e) => return skip_or_fail(e.code()),
+        };
+
+        if task_info.wait_cause() != Some(WaitCause::Mutex) {
+            return Ok(true);
+        }
+        let mutex_id = match task_info.wait_object_id() {
+            Some(id) => id,
+            None => return Ok(true),
+        };
+
+        // Safety: We only query the mutex's state
+        let owner_id = match unsafe { MutexRef::from_raw_nonnull(mutex_id) }.info() {
+            Ok(info) => info.owning_task_id(),
+            Err(e) => return skip_or_fail(e.code()),
+        };
+        let owner_id = match owner_id {
+            Some(id) => id,
+            None => return Ok(true),
+        };
+
+        match buf.get_mut(len) {
+            Some(slot) => *slot = MaybeUninit::new(DeadlockEdge { task_id, mutex_id }),
+            None => return Ok(true),
+        }
+        len += 1;
+
+        // Safety: The first `len` elements are initialized
+        let edges = unsafe {
+            &*(&buf[..len] as *const [MaybeUninit<DeadlockEdge>] as *const [DeadlockEdge])
+        };
+
+        if owner_id == start {
+            if edges.iter().all(|edge| edge.task_id >= start) {
+                on_deadlock(edges);
+            }
+            return Ok(true);
+        }
+
+        if edges.iter().any(|edge| edge.task_id == owner_id) {
+            // `start` leads to a cycle but isn't a part of it
+            return Ok(true);
+        }
+
+        task_id = owner_id;
+    }
+}
+
+/// Handle an error from `ref_*`. An object that doesn't exist (anymore)
+/// ends the chain without failing.
+fn skip_or_fail(code: ErrorCode) -> Result<bool, Error<FindDeadlocksError>> {
+    match code.get() {
+        abi::E_ID | abi::E_NOEXS => Ok(true),
+        // Safety: The remaining error codes of `ref_*` are all covered by
+        // `FindDeadlocksError`
+        _ => Err(unsafe { Error::new_unchecked(code) }),
+    }
+}
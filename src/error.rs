@@ -2,7 +2,7 @@
 //!
 //! Errors indicate exceptional outcomes of system calls. Like most
 //! traditional operating systems, they are represented by error codes in
-//! μITRON.
+//! `μITRON`.
 //!
 //! # Error Kind Types
 //!
@@ -1,10 +1,825 @@
-//! Eventflags (TODO)
-// TODO: acre_flg
-// TODO: del_flg
-// TODO: set_flg
-// TODO: clr_flg
-// TODO: wai_flg
-// TODO: pol_flg
-// TODO: twai_flg
-// TODO: ini_flg
-// TODO: ref_flg
+//! Eventflags
+use core::{fmt, marker::PhantomData, mem::MaybeUninit};
+
+use crate::{
+    abi,
+    error::{Error, ErrorCode, ErrorKind, Kind},
+    time::Timeout,
+};
+
+define_error_kind! {
+    /// Error type for [`EventflagRef::set`].
+    pub enum SetError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for SetError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`EventflagRef::clear`].
+    pub enum ClearError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for ClearError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`EventflagRef::wait`].
+    pub enum WaitError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        /// The task is a restricted task.
+        #[cfg(all(not(feature = "none"), feature = "rstr_task"))]
+        NotSupported,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The specified bit pattern is zero.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        /// Another task is already waiting on the eventflag, which does not
+        /// allow multiple waiting tasks (`TA_WMUL`).
+        #[cfg(not(feature = "none"))]
+        Occupied,
+        #[cfg(not(feature = "none"))]
+        Released,
+        #[cfg(not(feature = "none"))]
+        TerminateRequest,
+        #[cfg(all(not(feature = "none"), feature = "dcre"))]
+        Deleted,
+    }
+}
+
+impl ErrorKind for WaitError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(all(not(feature = "none"), feature = "rstr_task"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ILUSE => Some(Self::Occupied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_RLWAI => Some(Self::Released(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_RASTER => Some(Self::TerminateRequest(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(all(not(feature = "none"), feature = "dcre"))]
+            abi::E_DLT => Some(Self::Deleted(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`EventflagRef::wait_timeout`].
+    pub enum WaitTimeoutError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        /// The task is a restricted task.
+        #[cfg(all(not(feature = "none"), feature = "rstr_task"))]
+        NotSupported,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The specified bit pattern is zero.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        /// Another task is already waiting on the eventflag, which does not
+        /// allow multiple waiting tasks (`TA_WMUL`).
+        #[cfg(not(feature = "none"))]
+        Occupied,
+        #[cfg(not(feature = "none"))]
+        Timeout,
+        #[cfg(not(feature = "none"))]
+        Released,
+        #[cfg(not(feature = "none"))]
+        TerminateRequest,
+        #[cfg(all(not(feature = "none"), feature = "dcre"))]
+        Deleted,
+    }
+}
+
+impl ErrorKind for WaitTimeoutError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            // `E_PAR` caused by an invalid timeout value is considered
+            // critical, so it's safe to assume it's caused by a zero pattern
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(all(not(feature = "none"), feature = "rstr_task"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ILUSE => Some(Self::Occupied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_TMOUT => Some(Self::Timeout(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_RLWAI => Some(Self::Released(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_RASTER => Some(Self::TerminateRequest(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(all(not(feature = "none"), feature = "dcre"))]
+            abi::E_DLT => Some(Self::Deleted(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`EventflagRef::poll`].
+    pub enum PollError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The specified bit pattern is zero.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        /// Another task is already waiting on the eventflag, which does not
+        /// allow multiple waiting tasks (`TA_WMUL`).
+        #[cfg(not(feature = "none"))]
+        Occupied,
+        #[cfg(not(feature = "none"))]
+        Timeout,
+    }
+}
+
+impl ErrorKind for PollError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ILUSE => Some(Self::Occupied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_TMOUT => Some(Self::Timeout(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`EventflagRef::initialize`].
+    pub enum InitializeError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for InitializeError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`EventflagRef::info`].
+    pub enum InfoError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for InfoError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`Eventflag::build`].
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub enum BuildError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// Ran out of eventflag IDs.
+        #[cfg(not(feature = "none"))]
+        OutOfMemory,
+        /// Bad parameter.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+#[cfg(feature = "dcre")]
+impl ErrorKind for BuildError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            // `E_MACV` is considered critical, hence excluded
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOID => Some(Self::OutOfMemory(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR | abi::E_RSATR => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`EventflagRef::delete`].
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub enum DeleteError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+#[cfg(feature = "dcre")]
+impl ErrorKind for DeleteError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+/// Eventflag bit pattern.
+pub type Pattern = abi::FLGPTN;
+
+/// Specifies the condition to wait for in [`EventflagRef::wait`] and its
+/// variants.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitMode {
+    /// Wait until any of the specified bits are set.
+    Any,
+    /// Wait until all of the specified bits are set.
+    All,
+}
+
+impl WaitMode {
+    /// Convert `self` to a value of type [`abi::MODE`].
+    #[inline]
+    pub fn as_raw(self) -> abi::MODE {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => match self {
+                Self::Any => abi::TWF_ORW,
+                Self::All => abi::TWF_ANDW,
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// Eventflag information returned by [`EventflagRef::info`].
+#[derive(Debug, Clone, Copy)]
+pub struct Info {
+    #[cfg(not(feature = "none"))]
+    raw: abi::T_RFLG,
+}
+
+impl Info {
+    /// Get the eventflag's current bit pattern.
+    #[inline]
+    pub fn pattern(&self) -> Pattern {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => self.raw.flgptn,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the first waiting task's ID.
+    #[inline]
+    pub fn first_waiting_task_id(&self) -> Option<abi::NonNullID> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => abi::NonNullID::new(self.raw.wtskid),
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// A borrowed reference to an eventflag.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct EventflagRef<'a> {
+    id: abi::NonNullID,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl fmt::Debug for EventflagRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Eventflag({})", self.id)
+    }
+}
+
+/// # Object ID conversion
+impl EventflagRef<'_> {
+    /// Construct a `EventflagRef` from a raw object ID.
+    ///
+    /// # Safety
+    ///
+    /// See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
+        Self {
+            id,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get the raw object ID.
+    #[inline]
+    pub const fn as_raw(self) -> abi::ID {
+        self.id.get()
+    }
+
+    /// Get the raw object ID as [` abi::NonNullID`].
+    #[inline]
+    pub const fn as_raw_nonnull(self) -> abi::NonNullID {
+        self.id
+    }
+}
+
+/// # Management
+impl EventflagRef<'_> {
+    /// `del_flg`: Delete the eventflag.
+    ///
+    /// # Safety
+    ///
+    /// See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    #[doc(alias = "del_flg")]
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub unsafe fn delete(self) -> Result<(), Error<DeleteError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::del_flg(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `ref_flg`: Get the eventflag's general information.
+    #[inline]
+    #[doc(alias = "ref_flg")]
+    pub fn info(self) -> Result<Info, Error<InfoError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut pri = MaybeUninit::uninit();
+                Error::err_if_negative(abi::ref_flg(self.as_raw(), pri.as_mut_ptr()))?;
+                Ok(Info {
+                    raw: pri.assume_init(),
+                })
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// # Eventflag Operations
+impl EventflagRef<'_> {
+    /// `set_flg`: Set the specified bits.
+    #[inline]
+    #[doc(alias = "set_flg")]
+    pub fn set(self, bits: Pattern) -> Result<(), Error<SetError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::set_flg(self.as_raw(), bits))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `clr_flg`: Clear the specified bits.
+    ///
+    /// Note that the parameter is the complement of what `clr_flg` takes;
+    /// `clear(bits)` is equivalent to `clr_flg(flgid, !bits)`.
+    #[inline]
+    #[doc(alias = "clr_flg")]
+    pub fn clear(self, bits: Pattern) -> Result<(), Error<ClearError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::clr_flg(self.as_raw(), !bits))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `wai_flg`: Wait until the specified condition is met. Blocks the
+    /// current task if the condition is not met yet.
+    ///
+    /// Returns the bit pattern at the point when the condition was met.
+    #[inline]
+    #[doc(alias = "wai_flg")]
+    pub fn wait(self, bits: Pattern, mode: WaitMode) -> Result<Pattern, Error<WaitError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut out = MaybeUninit::uninit();
+                Error::err_if_negative(abi::wai_flg(
+                    self.as_raw(),
+                    bits,
+                    mode.as_raw(),
+                    out.as_mut_ptr(),
+                ))?;
+                Ok(out.assume_init())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `twai_flg`: Wait until the specified condition is met. Blocks the
+    /// current task with timeout if the condition is not met yet.
+    ///
+    /// Returns the bit pattern at the point when the condition was met.
+    #[inline]
+    #[doc(alias = "twai_flg")]
+    pub fn wait_timeout(
+        self,
+        bits: Pattern,
+        mode: WaitMode,
+        tmo: Timeout,
+    ) -> Result<Pattern, Error<WaitTimeoutError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut out = MaybeUninit::uninit();
+                Error::err_if_negative(abi::twai_flg(
+                    self.as_raw(),
+                    bits,
+                    mode.as_raw(),
+                    out.as_mut_ptr(),
+                    tmo.as_raw(),
+                ))?;
+                Ok(out.assume_init())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `pol_flg`: Check if the specified condition is met. Fails and returns
+    /// immediately if the condition is not met yet.
+    ///
+    /// Returns the bit pattern at the point when the condition was met.
+    #[inline]
+    #[doc(alias = "pol_flg")]
+    pub fn poll(self, bits: Pattern, mode: WaitMode) -> Result<Pattern, Error<PollError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut out = MaybeUninit::uninit();
+                Error::err_if_negative(abi::pol_flg(
+                    self.as_raw(),
+                    bits,
+                    mode.as_raw(),
+                    out.as_mut_ptr(),
+                ))?;
+                Ok(out.assume_init())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `ini_flg`: Initialize the eventflag.
+    #[inline]
+    #[doc(alias = "ini_flg")]
+    pub fn initialize(self) -> Result<(), Error<InitializeError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::ini_flg(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+#[cfg(feature = "dcre")]
+pub use self::owned::*;
+
+#[cfg(feature = "dcre")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+mod owned {
+    use super::*;
+    use crate::wait::QueueOrder;
+
+    /// The builder type for [eventflags](Eventflag). Created by [`Eventflag::build`].
+    ///
+    /// Its generic parameters are an implementation detail.
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    #[must_use = "`Builder` creates nothing unless you call `.finish()`"]
+    pub struct Builder {
+        #[cfg(not(feature = "none"))]
+        raw: abi::T_CFLG,
+    }
+
+    impl Eventflag {
+        /// `acre_flg`: Create a builder for `Eventflag`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// use itron::eventflag::{Eventflag, WaitMode};
+        /// let eventflag = Eventflag::build()
+        ///     .initial_pattern(0b01)
+        ///     .finish()
+        ///     .expect("failed to create an eventflag");
+        ///
+        /// eventflag.as_ref().set(0b10)
+        ///    .expect("failed to set bits");
+        /// eventflag.as_ref().wait(0b11, WaitMode::All)
+        ///    .expect("failed to perform a wait operation");
+        ///
+        /// eventflag.as_ref().clear(0b01)
+        ///    .expect("failed to clear bits");
+        /// eventflag.as_ref().poll(0b11, WaitMode::All)
+        ///    .expect_err("unexpectedly succeeded to perform a polling wait operation");
+        /// ```
+        ///
+        /// ```rust,no_run
+        /// use itron::{eventflag::{Eventflag, WaitMode}, wait::QueueOrder};
+        /// let eventflag = Eventflag::build()
+        ///     .multiple_waiters(true)
+        ///     .clear_on_release(true)
+        ///     .queue_order(QueueOrder::TaskPriority)
+        ///     .finish()
+        ///     .expect("failed to create an eventflag");
+        ///
+        /// eventflag.as_ref().set(0b100)
+        ///    .expect("failed to set bits");
+        /// eventflag.as_ref().poll(0b110, WaitMode::Any)
+        ///    .expect("failed to perform a polling wait operation");
+        /// eventflag.as_ref().poll(0b110, WaitMode::Any)
+        ///    .expect_err("the bits should have been cleared by the previous operation");
+        /// ```
+        #[inline]
+        #[doc(alias = "acre_flg")]
+        pub fn build() -> Builder {
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CFLG {
+                    flgatr: abi::TA_NULL,
+                    iflgptn: 0,
+                },
+            }
+        }
+    }
+
+    impl Builder {
+        /// Specify the initial bit pattern. Defaults to `0` when unspecified.
+        #[inline]
+        pub fn initial_pattern(self, value: Pattern) -> Builder {
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CFLG {
+                    iflgptn: value,
+                    ..self.raw
+                },
+            }
+        }
+
+        /// Specify whether more than one task can wait on the eventflag at
+        /// the same time. Defaults to `false` when unspecified.
+        #[inline]
+        #[doc(alias = "TA_WMUL")]
+        pub fn multiple_waiters(self, value: bool) -> Builder {
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CFLG {
+                    flgatr: if value {
+                        self.raw.flgatr | abi::TA_WMUL
+                    } else {
+                        self.raw.flgatr & !abi::TA_WMUL
+                    },
+                    ..self.raw
+                },
+            }
+        }
+
+        /// Specify whether the bit pattern is cleared entirely when a waiting
+        /// task is released. Defaults to `false` when unspecified.
+        #[inline]
+        #[doc(alias = "TA_CLR")]
+        pub fn clear_on_release(self, value: bool) -> Builder {
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CFLG {
+                    flgatr: if value {
+                        self.raw.flgatr | abi::TA_CLR
+                    } else {
+                        self.raw.flgatr & !abi::TA_CLR
+                    },
+                    ..self.raw
+                },
+            }
+        }
+
+        /// Specify the queue order. Defaults to `Fifo` when unspecified.
+        #[inline]
+        pub fn queue_order(self, value: QueueOrder) -> Builder {
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CFLG {
+                    flgatr: (self.raw.flgatr & !abi::TA_TPRI) | value.as_raw_atr(),
+                    ..self.raw
+                },
+            }
+        }
+    }
+
+    impl Builder {
+        /// Create an eventflag using the specified parameters.
+        pub fn finish(self) -> Result<Eventflag, Error<BuildError>> {
+            match () {
+                #[cfg(not(feature = "none"))]
+                () => unsafe {
+                    let id = Error::err_if_negative(abi::acre_flg(&self.raw))?;
+                    // Safety: We own the eventflag we create
+                    Ok(Eventflag::from_raw_nonnull(abi::NonNullID::new_unchecked(
+                        id,
+                    )))
+                },
+                #[cfg(feature = "none")]
+                () => unimplemented!(),
+            }
+        }
+    }
+
+    /// An owned eventflag.
+    ///
+    /// [Deletes] the eventflag automatically when dropped. The destructor will
+    /// panic if the deletion fails.
+    ///
+    /// [Deletes]: EventflagRef::delete
+    #[derive(PartialEq, Eq)]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub struct Eventflag(EventflagRef<'static>);
+
+    impl fmt::Debug for Eventflag {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    impl Drop for Eventflag {
+        #[inline]
+        fn drop(&mut self) {
+            unsafe { self.0.delete().unwrap() };
+        }
+    }
+
+    impl Eventflag {
+        /// Construct a `Eventflag` from a raw object ID.
+        ///
+        /// # Safety
+        ///
+        /// See [Object ID Wrappers](crate#object-id-wrappers).
+        #[inline]
+        pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
+            Self(unsafe { EventflagRef::from_raw_nonnull(id) })
+        }
+
+        /// Consume and "leak" `self`, returning a reference `EventflagRef<'a>`.
+        #[inline]
+        pub const fn leak<'a>(self) -> EventflagRef<'a> {
+            let out = self.0;
+            core::mem::forget(self);
+            out
+        }
+
+        /// Get the raw object ID.
+        #[inline]
+        pub const fn as_raw(&self) -> abi::ID {
+            self.0.as_raw()
+        }
+
+        /// Get the raw object ID as [` abi::NonNullID`].
+        #[inline]
+        pub const fn as_raw_nonnull(&self) -> abi::NonNullID {
+            self.0.as_raw_nonnull()
+        }
+
+        /// Borrow `Eventflag` as [`EventflagRef`].
+        ///
+        /// Use this to perform operations on eventflags because most of the
+        /// methods are implemented on `EventflagRef` but not `Eventflag`.
+        #[inline]
+        pub const fn as_ref(&self) -> EventflagRef<'_> {
+            self.0
+        }
+    }
+}
@@ -1,10 +1,629 @@
-//! Interrupts (TODO)
-// TODO: acre_isr
-// TODO: del_isr
-// TODO: dis_int
-// TODO: ena_int
-// TODO: clr_int
-// TODO: ras_int
-// TODO: prb_int
+//! Interrupts
+#[cfg(feature = "dcre")]
+use core::{fmt, marker::PhantomData};
+
+use crate::{
+    abi,
+    error::{Error, ErrorCode, ErrorKind, Kind},
+};
+
+define_error_kind! {
+    /// Error type for [`InterruptLine::disable`].
+    pub enum DisableError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        /// Disabling the interrupt line is not supported.
+        #[cfg(not(feature = "none"))]
+        NotSupported,
+        /// The interrupt number is out of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        /// The interrupt line is not configured.
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+impl ErrorKind for DisableError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`InterruptLine::enable`].
+    pub enum EnableError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        /// Enabling the interrupt line is not supported.
+        #[cfg(not(feature = "none"))]
+        NotSupported,
+        /// The interrupt number is out of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        /// The interrupt line is not configured.
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+impl ErrorKind for EnableError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`InterruptLine::clear`].
+    pub enum ClearError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        /// Clearing the interrupt request is not supported for the interrupt
+        /// line.
+        #[cfg(not(feature = "none"))]
+        NotSupported,
+        /// The interrupt number is out of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        /// The interrupt line is not configured.
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+impl ErrorKind for ClearError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`InterruptLine::raise`].
+    pub enum RaiseError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        /// Raising an interrupt request is not supported for the interrupt
+        /// line.
+        #[cfg(not(feature = "none"))]
+        NotSupported,
+        /// The interrupt number is out of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        /// The interrupt line is not configured.
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+impl ErrorKind for RaiseError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`InterruptLine::is_pending`].
+    pub enum IsPendingError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        /// Probing the interrupt request is not supported for the interrupt
+        /// line.
+        #[cfg(not(feature = "none"))]
+        NotSupported,
+        /// The interrupt number is out of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        /// The interrupt line is not configured.
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+impl ErrorKind for IsPendingError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`Isr::build`].
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub enum BuildError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// Ran out of interrupt service routine IDs.
+        #[cfg(not(feature = "none"))]
+        OutOfMemory,
+        /// Bad parameter, e.g., the interrupt number or the priority is out
+        /// of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        /// The interrupt line is not configured.
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+#[cfg(feature = "dcre")]
+impl ErrorKind for BuildError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            // `E_MACV` is considered critical, hence excluded
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOID => Some(Self::OutOfMemory(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR | abi::E_RSATR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`IsrRef::delete`].
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub enum DeleteError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The interrupt service routine was statically created.
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+#[cfg(feature = "dcre")]
+impl ErrorKind for DeleteError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+/// Refers to an interrupt request line.
+///
+/// Which operations are supported for a given interrupt line depends on the
+/// target hardware. Unsupported operations fail with `NotSupported`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct InterruptLine(abi::INTNO);
+
+impl InterruptLine {
+    /// Construct an `InterruptLine` from a raw interrupt number.
+    #[inline]
+    pub const fn from_raw(raw: abi::INTNO) -> Self {
+        Self(raw)
+    }
+
+    /// Get the raw interrupt number.
+    #[inline]
+    pub const fn as_raw(self) -> abi::INTNO {
+        self.0
+    }
+}
+
+/// # Interrupt Line Operations
+impl InterruptLine {
+    /// `dis_int`: Disable the interrupt line (i.e., set its interrupt request
+    /// disable flag).
+    #[inline]
+    #[doc(alias = "dis_int")]
+    pub fn disable(self) -> Result<(), Error<DisableError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::dis_int(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `ena_int`: Enable the interrupt line (i.e., clear its interrupt request
+    /// disable flag).
+    #[inline]
+    #[doc(alias = "ena_int")]
+    pub fn enable(self) -> Result<(), Error<EnableError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::ena_int(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `clr_int`: Clear the interrupt line's pending interrupt request.
+    #[inline]
+    #[doc(alias = "clr_int")]
+    pub fn clear(self) -> Result<(), Error<ClearError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::clr_int(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `ras_int`: Raise an interrupt request on the interrupt line.
+    #[inline]
+    #[doc(alias = "ras_int")]
+    pub fn raise(self) -> Result<(), Error<RaiseError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::ras_int(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `prb_int`: Get a flag indicating whether the interrupt line has a
+    /// pending interrupt request.
+    #[inline]
+    #[doc(alias = "prb_int")]
+    pub fn is_pending(self) -> Result<bool, Error<IsPendingError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let pending = Error::err_if_negative(abi::prb_int(self.as_raw()))?;
+                Ok(pending != 0)
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// A borrowed reference to an interrupt service routine.
+#[cfg(feature = "dcre")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct IsrRef<'a> {
+    id: abi::NonNullID,
+    _phantom: PhantomData<&'a ()>,
+}
+
+#[cfg(feature = "dcre")]
+impl fmt::Debug for IsrRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Isr({})", self.id)
+    }
+}
+
+/// # Object ID conversion
+#[cfg(feature = "dcre")]
+impl IsrRef<'_> {
+    /// Construct a `IsrRef` from a raw object ID.
+    ///
+    /// # Safety
+    ///
+    /// See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
+        Self {
+            id,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get the raw object ID.
+    #[inline]
+    pub const fn as_raw(self) -> abi::ID {
+        self.id.get()
+    }
+
+    /// Get the raw object ID as [` abi::NonNullID`].
+    #[inline]
+    pub const fn as_raw_nonnull(self) -> abi::NonNullID {
+        self.id
+    }
+}
+
+/// # Management
+#[cfg(feature = "dcre")]
+impl IsrRef<'_> {
+    /// `del_isr`: Delete the interrupt service routine.
+    ///
+    /// # Safety
+    ///
+    /// See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    #[doc(alias = "del_isr")]
+    pub unsafe fn delete(self) -> Result<(), Error<DeleteError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::del_isr(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+#[cfg(feature = "dcre")]
+pub use self::owned::*;
+
+#[cfg(feature = "dcre")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+mod owned {
+    use super::*;
+
+    /// Interrupt service routine priority. A smaller value represents a
+    /// higher priority.
+    pub type IsrPriority = abi::PRI;
+
+    /// The builder type for [interrupt service routines](Isr).
+    /// Created by [`Isr::build`].
+    ///
+    /// Its generic parameters are an implementation detail.
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    #[must_use = "`Builder` creates nothing unless you call `.finish()`"]
+    pub struct Builder<Interrupt, Start> {
+        #[allow(dead_code)]
+        interrupt: Interrupt,
+        #[allow(dead_code)]
+        start: Start,
+        #[cfg(not(feature = "none"))]
+        raw: abi::T_CISR,
+    }
+
+    /// Builder field hole types
+    #[allow(non_camel_case_types)]
+    #[doc(hidden)]
+    pub mod builder_hole {
+        pub struct __interrupt_is_not_specified__;
+        pub struct __start_is_not_specified__;
+    }
+
+    impl Isr {
+        /// `acre_isr`: Create a builder for `Isr`.
+        ///
+        /// # Example
+        ///
+        /// ```rust,no_run
+        /// use itron::interrupt::{InterruptLine, Isr};
+        /// let line = InterruptLine::from_raw(42);
+        /// let captured_variable = 42u16;
+        /// let isr = Isr::build()
+        ///     .interrupt(line)
+        ///     .priority(1)
+        ///     .start(move || { let _ = captured_variable; })
+        ///     .finish()
+        ///     .expect("failed to register an interrupt service routine");
+        ///
+        /// line.enable().expect("failed to enable the interrupt line");
+        /// ```
+        #[inline]
+        #[doc(alias = "acre_isr")]
+        pub fn build() -> Builder<
+            builder_hole::__interrupt_is_not_specified__,
+            builder_hole::__start_is_not_specified__,
+        > {
+            Builder {
+                interrupt: builder_hole::__interrupt_is_not_specified__,
+                start: builder_hole::__start_is_not_specified__,
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CISR {
+                    isratr: abi::TA_NULL,
+                    exinf: abi::EXINF::uninit(),
+                    intno: 0,
+                    isr: None,
+                    isrpri: 1,
+                },
+            }
+        }
+    }
+
+    impl<Interrupt, Start> Builder<Interrupt, Start> {
+        /// (**Mandatory**) Specify the interrupt line to attach the interrupt
+        /// service routine to.
+        #[inline]
+        pub fn interrupt(self, value: InterruptLine) -> Builder<(), Start> {
+            Builder {
+                interrupt: (),
+                start: self.start,
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CISR {
+                    intno: value.as_raw(),
+                    ..self.raw
+                },
+            }
+        }
+
+        /// (**Mandatory**) Specify the entry point.
+        #[inline]
+        pub fn start(
+            self,
+            value: impl crate::closure::IntoClosure + Send,
+        ) -> Builder<Interrupt, ()> {
+            let (isr, exinf) = value.into_closure();
+            Builder {
+                interrupt: self.interrupt,
+                start: (),
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CISR {
+                    isr: Some(isr),
+                    exinf,
+                    ..self.raw
+                },
+            }
+        }
+
+        /// Specify the priority among the interrupt service routines attached
+        /// to the same interrupt line. Defaults to `1` when unspecified.
+        #[inline]
+        pub fn priority(self, value: IsrPriority) -> Self {
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CISR {
+                    isrpri: value,
+                    ..self.raw
+                },
+                ..self
+            }
+        }
+    }
+
+    impl Builder<(), ()> {
+        /// Register an interrupt service routine using the specified
+        /// parameters.
+        pub fn finish(self) -> Result<Isr, Error<BuildError>> {
+            match () {
+                #[cfg(not(feature = "none"))]
+                () => unsafe {
+                    let id = Error::err_if_negative(abi::acre_isr(&self.raw))?;
+                    // Safety: We own the interrupt service routine we create
+                    Ok(Isr::from_raw_nonnull(abi::NonNullID::new_unchecked(id)))
+                },
+                #[cfg(feature = "none")]
+                () => unimplemented!(),
+            }
+        }
+    }
+
+    /// An owned interrupt service routine.
+    ///
+    /// [Deletes] the interrupt service routine automatically when dropped.
+    /// The destructor will panic if the deletion fails.
+    ///
+    /// [Deletes]: IsrRef::delete
+    #[derive(PartialEq, Eq)]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub struct Isr(IsrRef<'static>);
+
+    impl fmt::Debug for Isr {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    impl Drop for Isr {
+        #[inline]
+        fn drop(&mut self) {
+            unsafe { self.0.delete().unwrap() };
+        }
+    }
+
+    impl Isr {
+        /// Construct a `Isr` from a raw object ID.
+        ///
+        /// # Safety
+        ///
+        /// See [Object ID Wrappers](crate#object-id-wrappers).
+        #[inline]
+        pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
+            Self(unsafe { IsrRef::from_raw_nonnull(id) })
+        }
+
+        /// Consume and "leak" `self`, returning a reference `IsrRef<'a>`.
+        #[inline]
+        pub const fn leak<'a>(self) -> IsrRef<'a> {
+            let out = self.0;
+            core::mem::forget(self);
+            out
+        }
+
+        /// Get the raw object ID.
+        #[inline]
+        pub const fn as_raw(&self) -> abi::ID {
+            self.0.as_raw()
+        }
+
+        /// Get the raw object ID as [` abi::NonNullID`].
+        #[inline]
+        pub const fn as_raw_nonnull(&self) -> abi::NonNullID {
+            self.0.as_raw_nonnull()
+        }
+
+        /// Borrow `Isr` as [`IsrRef`].
+        #[inline]
+        pub const fn as_ref(&self) -> IsrRef<'_> {
+            self.0
+        }
+    }
+}
+
 // TODO: chg_ipm
 // TODO: get_ipm
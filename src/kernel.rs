@@ -1,11 +1,21 @@
 //! Miscellaneous functions that are not associated to specific kernel objects.
-#[cfg(any())]
-use crate::error::Kind;
+use core::{convert::TryFrom, marker::PhantomData, mem::MaybeUninit};
+
 use crate::{
     abi,
-    error::{Error, ErrorCode, ErrorKind},
+    error::{Error, ErrorCode, ErrorKind, Kind},
+    processor::Processor,
+    task::{Priority, TaskRef},
 };
 
+mod snapshot;
+pub use self::snapshot::*;
+
+#[cfg(feature = "dcre")]
+mod timeslice;
+#[cfg(feature = "dcre")]
+pub use self::timeslice::*;
+
 define_error_kind! {
     /// Error type for [`exit`].
     pub enum ExitError {
@@ -25,16 +35,242 @@ impl ErrorKind for ExitError {
     }
 }
 
-// TODO: rot_rdq
-// TODO: mrot_rdq
-// TODO: get_lod
-// TODO: mget_lod
-// TODO: get_nth
-// TODO: mget_nth
-// TODO: loc_cpu
-// TODO: unl_cpu
-// TODO: dis_dsp
-// TODO: ena_dsp
+define_error_kind! {
+    /// Error type for [`rotate_ready_queue`].
+    pub enum RotateReadyQueueError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// The priority is out of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+impl ErrorKind for RotateReadyQueueError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+// Note: `rotate_ready_queue_on` reduces to `rotate_ready_queue` on a uniprocessor kernel
+define_error_kind! {
+    /// Error type for [`rotate_ready_queue_on`].
+    pub enum RotateReadyQueueOnError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// The priority is out of range, or the processor ID is invalid.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+impl ErrorKind for RotateReadyQueueOnError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+            abi::E_ID => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`ready_queue_len`].
+    pub enum ReadyQueueLenError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// The priority is out of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+impl ErrorKind for ReadyQueueLenError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+// Note: `ready_queue_len_on` reduces to `ready_queue_len` on a uniprocessor kernel
+define_error_kind! {
+    /// Error type for [`ready_queue_len_on`].
+    pub enum ReadyQueueLenOnError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// The priority is out of range, or the processor ID is invalid.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+impl ErrorKind for ReadyQueueLenOnError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+            abi::E_ID => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`nth_ready_task`].
+    pub enum NthReadyTaskError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// The priority is out of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+impl ErrorKind for NthReadyTaskError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+// Note: `nth_ready_task_on` reduces to `nth_ready_task` on a uniprocessor kernel
+define_error_kind! {
+    /// Error type for [`nth_ready_task_on`].
+    pub enum NthReadyTaskOnError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// The priority is out of range, or the processor ID is invalid.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+impl ErrorKind for NthReadyTaskOnError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+            abi::E_ID => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`lock_cpu`] and [`with_cpu_lock`].
+    pub enum LockCpuError {
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for LockCpuError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`disable_dispatch`].
+    pub enum DisableDispatchError {
+        /// The current thread is not in a task context, or the CPU lock
+        /// state is active.
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for DisableDispatchError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`DispatchGuard`]'s destructor.
+    pub enum EnableDispatchError {
+        /// The current thread is not in a task context, or the CPU lock
+        /// state is active.
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for EnableDispatchError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
 
 /// `sns_ctx`: Get a flag indicating whether the current thread is in a task
 /// context.
@@ -115,3 +351,399 @@ pub fn exit() -> Error<ExitError> {
         () => unimplemented!(),
     }
 }
+
+/// `loc_cpu`: Activate the CPU lock state, returning a guard that restores
+/// the previous state when dropped.
+///
+/// If the CPU lock state is already active, the returned guard does nothing
+/// when dropped, so this function can be nested. Nested guards must be
+/// dropped in the reverse order of creation. Dropping the outermost guard
+/// deactivates the CPU lock state even if inner guards are still alive.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use itron::kernel;
+/// let guard = kernel::lock_cpu().expect("failed to activate the CPU lock state");
+/// assert!(kernel::is_cpu_lock_active());
+/// drop(guard);
+/// ```
+#[inline]
+#[doc(alias = "loc_cpu")]
+pub fn lock_cpu() -> Result<CpuLockGuard, Error<LockCpuError>> {
+    match () {
+        #[cfg(not(feature = "none"))]
+        () => unsafe {
+            let was_active = is_cpu_lock_active();
+            if !was_active {
+                Error::err_if_negative(abi::loc_cpu())?;
+            }
+            Ok(CpuLockGuard {
+                was_active,
+                _no_send: PhantomData,
+            })
+        },
+        #[cfg(feature = "none")]
+        () => unimplemented!(),
+    }
+}
+
+/// Call the specified closure in the CPU lock state.
+///
+/// This is a shorthand for holding a guard returned by [`lock_cpu`] during
+/// the call.
+#[inline]
+pub fn with_cpu_lock<R>(f: impl FnOnce() -> R) -> Result<R, Error<LockCpuError>> {
+    let _guard = lock_cpu()?;
+    Ok(f())
+}
+
+/// The RAII guard returned by [`lock_cpu`]. Deactivates the CPU lock state
+/// (`unl_cpu`) when dropped unless it was already active when the guard was
+/// created.
+///
+/// This type is `!Send` because the CPU lock state is specific to the
+/// current processor.
+#[derive(Debug)]
+#[must_use = "the CPU lock state is deactivated immediately if the guard is dropped"]
+pub struct CpuLockGuard {
+    was_active: bool,
+    _no_send: PhantomData<*mut ()>,
+}
+
+impl Drop for CpuLockGuard {
+    #[inline]
+    fn drop(&mut self) {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => {
+                if !self.was_active {
+                    unsafe { abi::unl_cpu() };
+                }
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// `dis_dsp`: Disable dispatching, returning a guard that restores the
+/// previous state when dropped.
+///
+/// If dispatching is already disabled, the returned guard does nothing when
+/// dropped, so this function can be nested. Nested guards must be dropped in
+/// the reverse order of creation. Dropping the outermost guard enables
+/// dispatching even if inner guards are still alive.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use itron::kernel;
+/// let guard = kernel::disable_dispatch().expect("failed to disable dispatching");
+/// assert!(kernel::is_dispatching_disabled());
+/// drop(guard);
+/// ```
+#[inline]
+#[doc(alias = "dis_dsp")]
+pub fn disable_dispatch() -> Result<DispatchGuard, Error<DisableDispatchError>> {
+    match () {
+        #[cfg(not(feature = "none"))]
+        () => unsafe {
+            let was_disabled = is_dispatching_disabled();
+            Error::err_if_negative(abi::dis_dsp())?;
+            Ok(DispatchGuard {
+                was_disabled,
+                _no_send: PhantomData,
+            })
+        },
+        #[cfg(feature = "none")]
+        () => unimplemented!(),
+    }
+}
+
+/// The RAII guard returned by [`disable_dispatch`]. Enables dispatching
+/// (`ena_dsp`) when dropped unless it was already disabled when the guard
+/// was created. The destructor will panic if this fails, e.g., because the
+/// CPU lock state is active.
+///
+/// This type is `!Send` because the dispatching state is specific to the
+/// current processor.
+#[derive(Debug)]
+#[must_use = "dispatching is enabled immediately if the guard is dropped"]
+pub struct DispatchGuard {
+    was_disabled: bool,
+    _no_send: PhantomData<*mut ()>,
+}
+
+impl Drop for DispatchGuard {
+    #[inline]
+    fn drop(&mut self) {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => {
+                if !self.was_disabled {
+                    unsafe { Error::<EnableDispatchError>::err_if_negative(abi::ena_dsp()) }
+                        .unwrap();
+                }
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// `rot_rdq`: Move the first task in the ready queue of the specified
+/// priority to the end of the queue.
+///
+/// On a multiprocessor kernel, this operates on the current processor's ready
+/// queue.
+#[inline]
+#[doc(alias = "rot_rdq")]
+pub fn rotate_ready_queue(priority: Priority) -> Result<(), Error<RotateReadyQueueError>> {
+    match () {
+        #[cfg(not(feature = "none"))]
+        () => unsafe {
+            Error::err_if_negative(abi::rot_rdq(priority))?;
+            Ok(())
+        },
+        #[cfg(feature = "none")]
+        () => unimplemented!(),
+    }
+}
+
+/// `mrot_rdq`: Move the first task in the specified processor's ready queue
+/// of the specified priority to the end of the queue.
+#[inline]
+#[doc(alias = "mrot_rdq")]
+pub fn rotate_ready_queue_on(
+    priority: Priority,
+    processor: Processor,
+) -> Result<(), Error<RotateReadyQueueOnError>> {
+    match () {
+        #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+        () => unsafe {
+            Error::err_if_negative(abi::mrot_rdq(priority, processor.as_raw()))?;
+            Ok(())
+        },
+        #[cfg(not(any(feature = "none", feature = "fmp3", feature = "solid_fmp3")))]
+        () => {
+            let Processor::UNIPROCESSOR = processor;
+            rotate_ready_queue(priority).map_err(|e| unsafe { Error::new_unchecked(e.code()) })
+        }
+        #[cfg(feature = "none")]
+        () => unimplemented!(),
+    }
+}
+
+/// `get_lod`: Get the number of tasks in the ready queue of the specified
+/// priority.
+///
+/// On a multiprocessor kernel, this operates on the current processor's ready
+/// queue.
+#[inline]
+#[doc(alias = "get_lod")]
+pub fn ready_queue_len(priority: Priority) -> Result<usize, Error<ReadyQueueLenError>> {
+    match () {
+        #[cfg(not(feature = "none"))]
+        () => unsafe {
+            let mut out = MaybeUninit::uninit();
+            Error::err_if_negative(abi::get_lod(priority, out.as_mut_ptr()))?;
+            Ok(out.assume_init() as usize)
+        },
+        #[cfg(feature = "none")]
+        () => unimplemented!(),
+    }
+}
+
+/// `mget_lod`: Get the number of tasks in the specified processor's ready
+/// queue of the specified priority.
+#[inline]
+#[doc(alias = "mget_lod")]
+pub fn ready_queue_len_on(
+    priority: Priority,
+    processor: Processor,
+) -> Result<usize, Error<ReadyQueueLenOnError>> {
+    match () {
+        #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+        () => unsafe {
+            let mut out = MaybeUninit::uninit();
+            Error::err_if_negative(abi::mget_lod(
+                processor.as_raw(),
+                priority,
+                out.as_mut_ptr(),
+            ))?;
+            Ok(out.assume_init() as usize)
+        },
+        #[cfg(not(any(feature = "none", feature = "fmp3", feature = "solid_fmp3")))]
+        () => {
+            let Processor::UNIPROCESSOR = processor;
+            ready_queue_len(priority).map_err(|e| unsafe { Error::new_unchecked(e.code()) })
+        }
+        #[cfg(feature = "none")]
+        () => unimplemented!(),
+    }
+}
+
+/// `get_nth`: Get the ID of the `n`-th task (zero-based) in the ready queue
+/// of the specified priority. Returns `None` if the queue has no more than
+/// `n` tasks.
+///
+/// On a multiprocessor kernel, this operates on the current processor's ready
+/// queue.
+#[inline]
+#[doc(alias = "get_nth")]
+pub fn nth_ready_task(
+    priority: Priority,
+    n: usize,
+) -> Result<Option<abi::NonNullID>, Error<NthReadyTaskError>> {
+    match () {
+        #[cfg(not(feature = "none"))]
+        () => unsafe {
+            let n = if let Ok(n) = abi::uint_t::try_from(n) {
+                n
+            } else {
+                return Ok(None);
+            };
+            let mut out = MaybeUninit::uninit();
+            Error::err_if_negative(abi::get_nth(priority, n, out.as_mut_ptr()))?;
+            Ok(abi::NonNullID::new(out.assume_init()))
+        },
+        #[cfg(feature = "none")]
+        () => unimplemented!(),
+    }
+}
+
+/// `mget_nth`: Get the ID of the `n`-th task (zero-based) in the specified
+/// processor's ready queue of the specified priority. Returns `None` if the
+/// queue has no more than `n` tasks.
+#[inline]
+#[doc(alias = "mget_nth")]
+pub fn nth_ready_task_on(
+    priority: Priority,
+    n: usize,
+    processor: Processor,
+) -> Result<Option<abi::NonNullID>, Error<NthReadyTaskOnError>> {
+    match () {
+        #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+        () => unsafe {
+            let n = if let Ok(n) = abi::uint_t::try_from(n) {
+                n
+            } else {
+                return Ok(None);
+            };
+            let mut out = MaybeUninit::uninit();
+            Error::err_if_negative(abi::mget_nth(
+                processor.as_raw(),
+                priority,
+                n,
+                out.as_mut_ptr(),
+            ))?;
+            Ok(abi::NonNullID::new(out.assume_init()))
+        },
+        #[cfg(not(any(feature = "none", feature = "fmp3", feature = "solid_fmp3")))]
+        () => {
+            let Processor::UNIPROCESSOR = processor;
+            nth_ready_task(priority, n).map_err(|e| unsafe { Error::new_unchecked(e.code()) })
+        }
+        #[cfg(feature = "none")]
+        () => unimplemented!(),
+    }
+}
+
+/// Get an iterator over the tasks in the ready queue of the specified
+/// priority, built on [`nth_ready_task`].
+///
+/// On a multiprocessor kernel, this operates on the current processor's ready
+/// queue.
+///
+/// The ready queue is not locked during the iteration. If the queue is
+/// modified in the meantime, the iterator may skip or repeat tasks. Activate
+/// the CPU lock state ([`lock_cpu`]) to get a consistent view.
+///
+/// # Safety
+///
+/// The iterator yields [`TaskRef`]s constructed from the task IDs found in
+/// the ready queue. See [Object ID Wrappers](crate#object-id-wrappers).
+/// Use [`nth_ready_task`] to get the task IDs without this requirement.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use itron::kernel;
+/// // Safety: We only query the tasks' states
+/// let tasks = unsafe { kernel::ready_tasks(4) }.expect("failed to query the ready queue");
+/// for task in tasks {
+///     println!("{:?}", task.info());
+/// }
+/// ```
+#[inline]
+pub unsafe fn ready_tasks<'a>(
+    priority: Priority,
+) -> Result<ReadyTasks<'a>, Error<NthReadyTaskError>> {
+    let first = nth_ready_task(priority, 0)?;
+    Ok(ReadyTasks {
+        priority,
+        processor: None,
+        next: first,
+        n: 1,
+        _phantom: PhantomData,
+    })
+}
+
+/// Get an iterator over the tasks in the specified processor's ready queue
+/// of the specified priority, built on [`nth_ready_task_on`].
+///
+/// See [`ready_tasks`] for caveats.
+///
+/// # Safety
+///
+/// See [`ready_tasks`].
+#[inline]
+pub unsafe fn ready_tasks_on<'a>(
+    priority: Priority,
+    processor: Processor,
+) -> Result<ReadyTasks<'a>, Error<NthReadyTaskOnError>> {
+    let first = nth_ready_task_on(priority, 0, processor)?;
+    Ok(ReadyTasks {
+        priority,
+        processor: Some(processor),
+        next: first,
+        n: 1,
+        _phantom: PhantomData,
+    })
+}
+
+/// The iterator returned by [`ready_tasks`] and [`ready_tasks_on`].
+///
+/// This type is `!Send` because the ready queue it refers to may depend on
+/// the current processor.
+#[derive(Debug, Clone)]
+pub struct ReadyTasks<'a> {
+    priority: Priority,
+    processor: Option<Processor>,
+    next: Option<abi::NonNullID>,
+    n: usize,
+    _phantom: PhantomData<(*mut (), &'a ())>,
+}
+
+impl<'a> Iterator for ReadyTasks<'a> {
+    type Item = TaskRef<'a>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let out = self.next?;
+        // The first call succeeded, and the context and the parameters
+        // haven't changed since then, so these calls won't fail
+        self.next = if let Some(processor) = self.processor {
+            nth_ready_task_on(self.priority, self.n, processor)
+                .ok()
+                .flatten()
+        } else {
+            nth_ready_task(self.priority, self.n).ok().flatten()
+        };
+        self.n += 1;
+        // Safety: Upheld by the caller of `ready_tasks[_on]`
+        Some(unsafe { TaskRef::from_raw_nonnull(out) })
+    }
+}
+
+impl core::iter::FusedIterator for ReadyTasks<'_> {}
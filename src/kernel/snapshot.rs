@@ -0,0 +1,228 @@
+//! Kernel state snapshots
+use core::{mem::MaybeUninit, slice};
+
+use super::*;
+use crate::{dataqueue, eventflag, memorypool, mutex, prioritydataqueue, semaphore, task};
+
+#[cfg(any(
+    all(feature = "asp3", feature = "messagebuf"),
+    all(feature = "solid_asp3", feature = "messagebuf"),
+    feature = "none",
+))]
+use crate::messagebuffer;
+
+define_error_kind! {
+    /// Error type for [`snapshot`].
+    pub enum SnapshotError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for SnapshotError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+/// A kernel object's state captured by [`snapshot`].
+#[derive(Debug, Clone, Copy)]
+#[non_exhaustive]
+pub enum Record {
+    /// A task.
+    Task(abi::NonNullID, task::Info),
+    /// A semaphore.
+    Semaphore(abi::NonNullID, semaphore::Info),
+    /// An eventflag.
+    Eventflag(abi::NonNullID, eventflag::Info),
+    /// A dataqueue.
+    Dataqueue(abi::NonNullID, dataqueue::Info),
+    /// A priority dataqueue.
+    PriorityDataqueue(abi::NonNullID, prioritydataqueue::Info),
+    /// A mutex.
+    Mutex(abi::NonNullID, mutex::Info),
+    /// A fixed-sized memory pool.
+    MemoryPool(abi::NonNullID, memorypool::Info),
+    /// A message buffer.
+    #[cfg(any(
+        all(feature = "asp3", feature = "messagebuf"),
+        all(feature = "solid_asp3", feature = "messagebuf"),
+        feature = "none",
+    ))]
+    #[cfg_attr(
+        feature = "doc_cfg",
+        doc(cfg(any(
+            all(feature = "asp3", feature = "messagebuf"),
+            all(feature = "solid_asp3", feature = "messagebuf")
+        )))
+    )]
+    MessageBuffer(abi::NonNullID, messagebuffer::Info),
+}
+
+impl Record {
+    /// Get the ID of the kernel object.
+    #[inline]
+    pub fn id(&self) -> abi::NonNullID {
+        match *self {
+            Self::Task(id, _)
+            | Self::Semaphore(id, _)
+            | Self::Eventflag(id, _)
+            | Self::Dataqueue(id, _)
+            | Self::PriorityDataqueue(id, _)
+            | Self::Mutex(id, _)
+            | Self::MemoryPool(id, _) => id,
+            #[cfg(any(
+                all(feature = "asp3", feature = "messagebuf"),
+                all(feature = "solid_asp3", feature = "messagebuf"),
+                feature = "none",
+            ))]
+            Self::MessageBuffer(id, _) => id,
+        }
+    }
+}
+
+/// The result of [`snapshot`].
+#[derive(Debug, Clone, Copy)]
+pub struct Snapshot<'a> {
+    records: &'a [Record],
+    is_truncated: bool,
+}
+
+impl<'a> Snapshot<'a> {
+    /// Get the captured records, ordered by object kind and then by ID.
+    #[inline]
+    pub fn records(&self) -> &'a [Record] {
+        self.records
+    }
+
+    /// Get a flag indicating whether the buffer was too small to hold all
+    /// records.
+    #[inline]
+    pub fn is_truncated(&self) -> bool {
+        self.is_truncated
+    }
+}
+
+/// Capture the state of every existing task and synchronization object by
+/// calling `ref_*` for each object ID in turn. The records are written to
+/// `buf`.
+///
+/// An ID that doesn't have an object (`E_NOEXS`) is skipped, and the walk of
+/// each object kind ends at the first out-of-range ID (`E_ID`). The captured
+/// states are not a consistent snapshot of the whole system because the
+/// system keeps running while they are collected.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use core::mem::MaybeUninit;
+/// use itron::kernel::{snapshot, Record};
+/// let mut buf = [MaybeUninit::<Record>::uninit(); 64];
+/// let snapshot = snapshot(&mut buf).expect("failed to take a snapshot");
+/// for record in snapshot.records() {
+///     if let Record::Task(id, info) = record {
+///         // do something with `info`
+///     }
+/// }
+/// ```
+pub fn snapshot(buf: &mut [MaybeUninit<Record>]) -> Result<Snapshot<'_>, Error<SnapshotError>> {
+    let mut writer = Writer { buf, len: 0 };
+
+    // Safety (the `from_raw_nonnull` calls): We only query the objects'
+    // states
+    let is_complete = walk(
+        &mut writer,
+        |id| unsafe { task::TaskRef::from_raw_nonnull(id) }.info(),
+        Record::Task,
+    )? && walk(
+        &mut writer,
+        |id| unsafe { semaphore::SemaphoreRef::from_raw_nonnull(id) }.info(),
+        Record::Semaphore,
+    )? && walk(
+        &mut writer,
+        |id| unsafe { eventflag::EventflagRef::from_raw_nonnull(id) }.info(),
+        Record::Eventflag,
+    )? && walk(
+        &mut writer,
+        |id| unsafe { dataqueue::DataqueueRef::from_raw_nonnull(id) }.info(),
+        Record::Dataqueue,
+    )? && walk(
+        &mut writer,
+        |id| unsafe { prioritydataqueue::PriorityDataqueueRef::from_raw_nonnull(id) }.info(),
+        Record::PriorityDataqueue,
+    )? && walk(
+        &mut writer,
+        |id| unsafe { mutex::MutexRef::from_raw_nonnull(id) }.info(),
+        Record::Mutex,
+    )? && walk(
+        &mut writer,
+        |id| unsafe { memorypool::MemoryPoolRef::from_raw_nonnull(id) }.info(),
+        Record::MemoryPool,
+    )?;
+
+    #[cfg(any(
+        all(feature = "asp3", feature = "messagebuf"),
+        all(feature = "solid_asp3", feature = "messagebuf"),
+        feature = "none",
+    ))]
+    let is_complete = is_complete
+        && walk(
+            &mut writer,
+            |id| unsafe { messagebuffer::MessageBufferRef::from_raw_nonnull(id) }.info(),
+            Record::MessageBuffer,
+        )?;
+
+    let Writer { buf, len } = writer;
+    Ok(Snapshot {
+        // Safety: The first `len` elements are initialized
+        records: unsafe { slice::from_raw_parts(buf.as_ptr() as *const Record, len) },
+        is_truncated: !is_complete,
+    })
+}
+
+struct Writer<'a> {
+    buf: &'a mut [MaybeUninit<Record>],
+    len: usize,
+}
+
+/// Call `info` for each object ID and push the results to `writer`. Returns
+/// `false` if `writer` ran out of space.
+fn walk<Info, K: ErrorKind>(
+    writer: &mut Writer<'_>,
+    info: impl Fn(abi::NonNullID) -> Result<Info, Error<K>>,
+    record: impl Fn(abi::NonNullID, Info) -> Record,
+) -> Result<bool, Error<SnapshotError>> {
+    let mut id = abi::NonNullID::new(1).unwrap();
+    loop {
+        match info(id) {
+            Ok(info) => {
+                if let Some(slot) = writer.buf.get_mut(writer.len) {
+                    *slot = MaybeUninit::new(record(id, info));
+                    writer.len += 1;
+                } else {
+                    return Ok(false);
+                }
+            }
+            Err(e) => match e.code().get() {
+                abi::E_ID => return Ok(true),
+                abi::E_NOEXS => {}
+                // Safety: The remaining error codes of `ref_*` are all
+                // covered by `SnapshotError`
+                _ => return Err(unsafe { Error::new_unchecked(e.code()) }),
+            },
+        }
+
+        id = match id.get().checked_add(1).and_then(abi::NonNullID::new) {
+            Some(id) => id,
+            None => return Ok(true),
+        };
+    }
+}
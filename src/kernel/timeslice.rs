@@ -0,0 +1,137 @@
+//! Round-robin time slicing
+use super::*;
+use crate::{
+    cyclic::{self, Cyclic, CyclicRef},
+    time::Duration,
+};
+
+/// A round-robin time-slicing service, which rotates the ready queues of
+/// the specified priorities every time quantum.
+///
+/// `μITRON` kernels schedule tasks of the same priority in a FIFO manner and
+/// do not preempt a running task in favor of another task of the same
+/// priority. `TimeSlicer` emulates time slicing by [rotating] the ready
+/// queues from a [cyclic handler] it owns. On a multiprocessor kernel, it
+/// rotates the ready queues of all processors.
+///
+/// The cyclic handler is deleted when `TimeSlicer` is dropped.
+///
+/// [rotating]: rotate_ready_queue
+/// [cyclic handler]: crate::cyclic
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use itron::{kernel::TimeSlicer, task::Priority, time::Duration};
+/// static PRIORITIES: [Priority; 2] = [8, 9];
+/// let time_slicer = TimeSlicer::new(&PRIORITIES, Duration::from_millis(10).unwrap())
+///     .expect("failed to create a time slicer");
+/// time_slicer.start().expect("failed to start time slicing");
+/// ```
+#[derive(Debug)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+pub struct TimeSlicer {
+    cyclic: Cyclic,
+}
+
+impl TimeSlicer {
+    /// Create a `TimeSlicer` that rotates the ready queues of `priorities`
+    /// every `quantum`. The created `TimeSlicer` is initially stopped.
+    ///
+    /// Fails with `BadParam` if any of `priorities` is out of range or
+    /// [`TPRI_SELF`](abi::TPRI_SELF).
+    pub fn new<const N: usize>(
+        priorities: &'static [Priority; N],
+        quantum: Duration,
+    ) -> Result<Self, Error<cyclic::BuildError>> {
+        validate_priorities(priorities)?;
+        let cyclic = Cyclic::build()
+            .start(move || rotate_ready_queues(priorities))
+            .period(quantum)
+            .finish()?;
+        Ok(Self { cyclic })
+    }
+
+    /// Start time slicing. The first rotation happens after `quantum`.
+    #[inline]
+    pub fn start(&self) -> Result<(), Error<cyclic::StartError>> {
+        self.cyclic.as_ref().start()
+    }
+
+    /// Stop time slicing.
+    #[inline]
+    pub fn stop(&self) -> Result<(), Error<cyclic::StopError>> {
+        self.cyclic.as_ref().stop()
+    }
+
+    /// Get a flag indicating whether time slicing is active.
+    #[inline]
+    pub fn is_active(&self) -> Result<bool, Error<cyclic::InfoError>> {
+        Ok(self.cyclic.as_ref().info()?.is_active())
+    }
+
+    /// Get the underlying cyclic handler.
+    #[inline]
+    pub fn as_cyclic(&self) -> CyclicRef<'_> {
+        self.cyclic.as_ref()
+    }
+}
+
+/// Check that `rotate_ready_queues` will accept `priorities`.
+fn validate_priorities(priorities: &[Priority]) -> Result<(), Error<cyclic::BuildError>> {
+    match () {
+        #[cfg(not(feature = "none"))]
+        () => {
+            for &priority in priorities {
+                // `TPRI_SELF` is rejected in a non-task context
+                if priority == abi::TPRI_SELF {
+                    // Safety: `E_PAR` is handled by `BuildError`
+                    return Err(unsafe {
+                        Error::new_unchecked(ErrorCode::new_unchecked(abi::E_PAR))
+                    });
+                }
+                // Let the kernel check the range
+                // Safety: The error codes of `get_lod` are all covered by
+                // `BuildError`
+                ready_queue_len(priority).map_err(|e| unsafe { Error::new_unchecked(e.code()) })?;
+            }
+            Ok(())
+        }
+        #[cfg(feature = "none")]
+        () => unimplemented!(),
+    }
+}
+
+/// The body of the cyclic handler. Errors are ignored because there's no
+/// one to report them to.
+fn rotate_ready_queues(priorities: &[Priority]) {
+    match () {
+        #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+        () => {
+            for &priority in priorities {
+                // Rotate every processor's ready queue. `mrot_rdq` fails with
+                // `E_ID` when we've gone past the last processor. Stop at any
+                // other error as well because it would recur for the
+                // remaining processors.
+                let mut prcid = 1;
+                while let Some(processor) = Processor::from_raw(prcid) {
+                    if rotate_ready_queue_on(priority, processor).is_err() {
+                        break;
+                    }
+                    prcid = match prcid.checked_add(1) {
+                        Some(prcid) => prcid,
+                        None => break,
+                    };
+                }
+            }
+        }
+        #[cfg(not(any(feature = "none", feature = "fmp3", feature = "solid_fmp3")))]
+        () => {
+            for &priority in priorities {
+                let _ = rotate_ready_queue(priority);
+            }
+        }
+        #[cfg(feature = "none")]
+        () => unimplemented!(),
+    }
+}
@@ -48,11 +48,16 @@ macro_rules! unstable_module {
 unstable_module! {
     #[macro_use]
     pub mod error;
+    pub mod alarm;
     pub mod closure;
+    pub mod cyclic;
     pub mod dataqueue;
+    pub mod diagnostics;
     pub mod eventflag;
     pub mod interrupt;
     pub mod kernel;
+    #[cfg(feature = "dcre")]
+    pub mod mailbox;
     pub mod memorypool;
     #[cfg(any(
         all(feature = "asp3", feature = "messagebuf"),
@@ -60,20 +65,32 @@ unstable_module! {
         feature = "none",
     ))]
     pub mod messagebuffer;
+    pub mod monitor;
     pub mod mutex;
     pub mod prioritydataqueue;
     pub mod processor;
     pub mod semaphore;
+    #[cfg(any(feature = "fmp3", feature = "solid_fmp3", feature = "none"))]
+    pub mod spinlock;
+    pub mod sync;
     pub mod task;
     pub mod wait;
-    // TODO: spinlocks
 
     /// Temporal quantification
     pub mod time {
         mod duration;
+        mod instant;
+        #[cfg(feature = "dcre")]
+        mod notify;
+        #[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
+        mod prctim;
         mod systime;
         mod timeout;
-        pub use self::{duration::*, systime::*, timeout::*};
+        pub use self::{duration::*, instant::*, systime::*, timeout::*};
+        #[cfg(feature = "dcre")]
+        pub use self::notify::*;
+        #[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
+        pub use self::prctim::*;
         // `use ::*` doesn't work with `pub macro`. This could be a bug.
         #[cfg(feature = "nightly")]
         pub use self::{duration::duration, timeout::timeout};
@@ -0,0 +1,225 @@
+//! Mailboxes
+//!
+//! The TOPPERS third-generation kernels don't provide mailboxes. [`Mailbox`]
+//! emulates them by storing messages in the memory blocks of a
+//! [memory pool](crate::memorypool) and sending pointers to the blocks
+//! through a [dataqueue](crate::dataqueue). Messages are not copied after
+//! they are sent.
+use core::{fmt, marker::PhantomData, mem, ptr::NonNull};
+
+use crate::{
+    abi,
+    dataqueue::{DataElement, Dataqueue, RecvError, RecvTimeoutError, TryRecvError},
+    error::{Error, ErrorCode, ErrorKind, Kind},
+    memorypool::{Block, GetError, GetTimeoutError, MemoryPool, PoolBox, TryGetError},
+    time::Timeout,
+};
+
+define_error_kind! {
+    /// Error type for [`Mailbox::new`].
+    pub enum BuildError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// The specified parameter is not supported by the kernel.
+        ///
+        ///  - On TOPPERS/HRP3, automatic allocation of a memory pool data
+        ///    storage is not supported (`E_NOSPT`, HRPS0199).
+        ///
+        #[cfg(any())]
+        NotSupported,
+        /// Ran out of memory or object IDs, or the specified capacity does
+        /// not fit in `uint_t`.
+        #[cfg(not(feature = "none"))]
+        OutOfMemory,
+        /// Bad parameter.
+        ///
+        ///  - `T`'s alignment is greater than that of memory blocks.
+        ///
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+impl ErrorKind for BuildError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            // `E_MACV` is considered critical, hence excluded
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOID | abi::E_NOMEM => Some(Self::OutOfMemory(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR | abi::E_RSATR => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+/// A mailbox carrying messages of type `T` between tasks.
+///
+/// The memory pool has as many memory blocks as the dataqueue's capacity, so
+/// sending a message only waits for a free memory block. A received message
+/// is returned as a [`PoolBox`], which returns the memory block to the pool
+/// when dropped. The messages remaining in the mailbox are dropped when the
+/// mailbox is dropped.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use itron::mailbox::Mailbox;
+/// let mailbox = Mailbox::new(4).expect("failed to create a mailbox");
+///
+/// mailbox.send([42u8; 64]).expect("failed to send a message");
+///
+/// let message = mailbox.recv().expect("failed to receive a message");
+/// assert_eq!(message[0], 42);
+/// ```
+pub struct Mailbox<T> {
+    pool: MemoryPool,
+    dataqueue: Dataqueue,
+    _phantom: PhantomData<T>,
+}
+
+unsafe impl<T: Send> Send for Mailbox<T> {}
+unsafe impl<T: Send> Sync for Mailbox<T> {}
+
+impl<T> fmt::Debug for Mailbox<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mailbox")
+            .field("pool", &self.pool)
+            .field("dataqueue", &self.dataqueue)
+            .finish()
+    }
+}
+
+impl<T> Mailbox<T> {
+    /// `acre_mpf` + `acre_dtq`: Create a mailbox that can hold up to
+    /// `capacity` messages, including the received messages that are still
+    /// alive.
+    pub fn new(capacity: usize) -> Result<Self, Error<BuildError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => {
+                if mem::align_of::<T>() > mem::align_of::<abi::MPF_T>() {
+                    // Safety: `E_PAR` is handled by `BuildError`
+                    // (Warning: This is not true for `cfg(feature = "none")`.)
+                    return Err(unsafe {
+                        Error::new_unchecked(ErrorCode::new_unchecked(abi::E_PAR))
+                    });
+                }
+
+                // Safety: The error codes of `acre_mpf` and `acre_dtq` are
+                // all covered by `BuildError`
+                let pool = MemoryPool::build()
+                    .block_size(mem::size_of::<T>().max(1))
+                    .block_count(capacity)
+                    .finish()
+                    .map_err(|e| unsafe { Error::new_unchecked(e.code()) })?;
+                let dataqueue = Dataqueue::build()
+                    .capacity(capacity)
+                    .finish()
+                    .map_err(|e| unsafe { Error::new_unchecked(e.code()) })?;
+
+                Ok(Self {
+                    pool,
+                    dataqueue,
+                    _phantom: PhantomData,
+                })
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Send a message. Blocks the current task if the mailbox is full.
+    ///
+    /// `value` is dropped if this method fails.
+    #[inline]
+    pub fn send(&self, value: T) -> Result<(), Error<GetError>> {
+        let block = self.pool.as_ref().get()?;
+        self.post(block, value);
+        Ok(())
+    }
+
+    /// [`send`](Self::send) with timeout.
+    #[inline]
+    pub fn send_timeout(&self, value: T, tmo: Timeout) -> Result<(), Error<GetTimeoutError>> {
+        let block = self.pool.as_ref().get_timeout(tmo)?;
+        self.post(block, value);
+        Ok(())
+    }
+
+    /// Send a message. Fails and returns immediately if the mailbox is full.
+    ///
+    /// `value` is dropped if this method fails.
+    #[inline]
+    pub fn try_send(&self, value: T) -> Result<(), Error<TryGetError>> {
+        let block = self.pool.as_ref().try_get()?;
+        self.post(block, value);
+        Ok(())
+    }
+
+    /// Receive a message. Blocks the current task if the mailbox is empty.
+    #[inline]
+    pub fn recv(&self) -> Result<PoolBox<'_, T>, Error<RecvError>> {
+        let data = self.dataqueue.as_ref().recv()?;
+        // Safety: `data` was sent by `post`
+        Ok(unsafe { self.unpost(data) })
+    }
+
+    /// [`recv`](Self::recv) with timeout.
+    #[inline]
+    pub fn recv_timeout(&self, tmo: Timeout) -> Result<PoolBox<'_, T>, Error<RecvTimeoutError>> {
+        let data = self.dataqueue.as_ref().recv_timeout(tmo)?;
+        // Safety: `data` was sent by `post`
+        Ok(unsafe { self.unpost(data) })
+    }
+
+    /// Receive a message. Fails and returns immediately if the mailbox is
+    /// empty.
+    #[inline]
+    pub fn try_recv(&self) -> Result<PoolBox<'_, T>, Error<TryRecvError>> {
+        let data = self.dataqueue.as_ref().try_recv()?;
+        // Safety: `data` was sent by `post`
+        Ok(unsafe { self.unpost(data) })
+    }
+
+    /// Move `value` into `block` and send `block` through the dataqueue.
+    fn post(&self, block: Block, value: T) {
+        let ptr = block as *mut T;
+        // Safety: `block` is large enough and suitably aligned for `T`
+        unsafe { ptr.write(value) };
+        // There are as many memory blocks as the dataqueue's capacity, so the
+        // dataqueue always has room for the block we are holding
+        self.dataqueue
+            .as_ref()
+            .try_send(ptr as DataElement)
+            .unwrap();
+    }
+
+    /// Take the ownership of a block sent by `post`.
+    ///
+    /// # Safety
+    ///
+    /// `data` must be a data element sent by `post` and received from the
+    /// dataqueue.
+    unsafe fn unpost(&self, data: DataElement) -> PoolBox<'_, T> {
+        unsafe { PoolBox::from_raw(self.pool.as_ref(), NonNull::new_unchecked(data as *mut T)) }
+    }
+}
+
+impl<T> Drop for Mailbox<T> {
+    fn drop(&mut self) {
+        // Drop the messages in flight
+        while let Ok(data) = self.dataqueue.as_ref().try_recv() {
+            // Safety: `data` was sent by `post`
+            drop(unsafe { self.unpost(data) });
+        }
+    }
+}
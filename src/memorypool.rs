@@ -496,6 +496,14 @@ impl MemoryPoolRef<'_> {
     }
 }
 
+mod boxed;
+pub use self::boxed::*;
+
+#[cfg(feature = "dcre")]
+mod typed;
+#[cfg(feature = "dcre")]
+pub use self::typed::*;
+
 #[cfg(feature = "dcre")]
 pub use self::owned::*;
 
@@ -571,8 +579,66 @@ mod owned {
         }
     }
 
+    /// A statically allocated storage for a memory pool created by
+    /// [`Builder::storage`]. It consists of `N` blocks of `BLOCK` bytes each
+    /// (`T_CMPF::mpf`) and their management area (`T_CMPF::mpfmb`).
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use itron::memorypool::{MemoryPool, MemoryPoolStorage};
+    /// static mut STORAGE: MemoryPoolStorage<32, 4> = MemoryPoolStorage::new();
+    /// let pool = MemoryPool::build()
+    ///     .storage(unsafe { &mut *core::ptr::addr_of_mut!(STORAGE) })
+    ///     .finish()
+    ///     .expect("failed to create a memory pool");
+    /// ```
+    #[repr(C)]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub struct MemoryPoolStorage<const BLOCK: usize, const N: usize> {
+        blocks: MaybeUninit<[BlockStorage<BLOCK>; N]>,
+        /// `MPFMB` consists of a single `uint_t`.
+        management: MaybeUninit<[abi::uint_t; N]>,
+    }
+
+    /// A memory block whose size is rounded up to a multiple of `MPF_T`'s
+    /// size, which is how the kernel lays out blocks.
+    #[repr(C)]
+    struct BlockStorage<const BLOCK: usize> {
+        _align: [abi::MPF_T; 0],
+        bytes: [u8; BLOCK],
+    }
+
+    impl<const BLOCK: usize, const N: usize> MemoryPoolStorage<BLOCK, N> {
+        /// Construct a `MemoryPoolStorage`.
+        #[inline]
+        pub const fn new() -> Self {
+            Self {
+                blocks: MaybeUninit::uninit(),
+                management: MaybeUninit::uninit(),
+            }
+        }
+    }
+
+    impl<const BLOCK: usize, const N: usize> Default for MemoryPoolStorage<BLOCK, N> {
+        #[inline]
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<const BLOCK: usize, const N: usize> fmt::Debug for MemoryPoolStorage<BLOCK, N> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("MemoryPoolStorage")
+                .field("block_size", &BLOCK)
+                .field("block_count", &N)
+                .finish()
+        }
+    }
+
     impl<BlockSize, BlockCount> Builder<BlockSize, BlockCount> {
-        /// (**Mandatory**) Specify the block size.
+        /// (**Mandatory**) Specify the block size. The kernel allocates the
+        /// memory pool's storage.
         #[inline]
         pub fn block_size(self, value: usize) -> Builder<(), BlockCount> {
             let (blksz, blksz_overflow) = match value.try_into() {
@@ -585,11 +651,17 @@ mod owned {
                 blksz_overflow,
                 blkcnt_overflow: self.blkcnt_overflow,
                 #[cfg(not(feature = "none"))]
-                raw: abi::T_CMPF { blksz, ..self.raw },
+                raw: abi::T_CMPF {
+                    blksz,
+                    mpf: core::ptr::null_mut(),
+                    mpfmb: core::ptr::null_mut(),
+                    ..self.raw
+                },
             }
         }
 
         /// (**Mandatory**) Specify the capacity, measured in number of blocks.
+        /// The kernel allocates the memory pool's storage.
         #[inline]
         pub fn block_count(self, value: usize) -> Builder<BlockSize, ()> {
             let (blkcnt, blkcnt_overflow) = match value.try_into() {
@@ -602,7 +674,31 @@ mod owned {
                 blksz_overflow: self.blksz_overflow,
                 blkcnt_overflow,
                 #[cfg(not(feature = "none"))]
-                raw: abi::T_CMPF { blkcnt, ..self.raw },
+                raw: abi::T_CMPF {
+                    blkcnt,
+                    mpf: core::ptr::null_mut(),
+                    mpfmb: core::ptr::null_mut(),
+                    ..self.raw
+                },
+            }
+        }
+
+        /// (**Mandatory**) Specify to use the specified storage. This also
+        /// sets the block size to `BLOCK` and the capacity to `N`.
+        #[inline]
+        pub fn storage<const BLOCK: usize, const N: usize>(
+            self,
+            storage: &'static mut MemoryPoolStorage<BLOCK, N>,
+        ) -> Builder<(), ()> {
+            let builder = self.block_size(BLOCK).block_count(N);
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CMPF {
+                    mpf: storage.blocks.as_mut_ptr() as *mut abi::MPF_T,
+                    mpfmb: storage.management.as_mut_ptr() as *mut u8,
+                    ..builder.raw
+                },
+                ..builder
             }
         }
 
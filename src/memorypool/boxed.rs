@@ -0,0 +1,104 @@
+use core::{
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    ptr::{self, NonNull},
+};
+
+use super::MemoryPoolRef;
+
+/// An owned pointer to a `T` stored in a memory block of a
+/// [memory pool](super).
+///
+/// Drops the `T` and [releases] the memory block when dropped. The
+/// destructor will panic if the releasing fails.
+///
+/// [releases]: MemoryPoolRef::release
+pub struct PoolBox<'a, T> {
+    ptr: NonNull<T>,
+    pool: MemoryPoolRef<'a>,
+    _phantom: PhantomData<T>,
+}
+
+unsafe impl<T: Send> Send for PoolBox<'_, T> {}
+unsafe impl<T: Sync> Sync for PoolBox<'_, T> {}
+
+impl<T: fmt::Debug> fmt::Debug for PoolBox<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<'a, T> PoolBox<'a, T> {
+    /// Construct a `PoolBox` from a pointer to a `T` stored in a memory
+    /// block of `pool`.
+    ///
+    /// # Safety
+    ///
+    /// `ptr` must point to a valid `T` stored at the beginning of a memory
+    /// block acquired from `pool`, and it must be suitably aligned for `T`.
+    /// The ownership of the `T` and the memory block is transferred to the
+    /// returned `PoolBox`.
+    #[inline]
+    pub const unsafe fn from_raw(pool: MemoryPoolRef<'a>, ptr: NonNull<T>) -> Self {
+        Self {
+            ptr,
+            pool,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Consume the `PoolBox`, returning the pointer to the `T`. The `T` is
+    /// not dropped, and the memory block is not released.
+    #[inline]
+    pub fn into_raw(this: Self) -> NonNull<T> {
+        let ptr = this.ptr;
+        core::mem::forget(this);
+        ptr
+    }
+
+    /// Get the memory pool owning the memory block.
+    #[inline]
+    pub fn pool(this: &Self) -> MemoryPoolRef<'a> {
+        this.pool
+    }
+
+    /// Consume the `PoolBox`, returning the `T` and releasing the memory
+    /// block.
+    #[inline]
+    pub fn into_inner(this: Self) -> T {
+        let pool = this.pool;
+        let ptr = Self::into_raw(this);
+        // Safety: We own the `T`, which is never used again
+        let value = unsafe { ptr::read(ptr.as_ptr()) };
+        pool.release(ptr.as_ptr() as _).unwrap();
+        value
+    }
+}
+
+impl<T> Deref for PoolBox<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // Safety: We own the `T`
+        unsafe { self.ptr.as_ref() }
+    }
+}
+
+impl<T> DerefMut for PoolBox<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: We own the `T`
+        unsafe { self.ptr.as_mut() }
+    }
+}
+
+impl<T> Drop for PoolBox<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        // Safety: We own the `T`, which is never used again
+        unsafe { ptr::drop_in_place(self.ptr.as_ptr()) };
+        self.pool.release(self.ptr.as_ptr() as _).unwrap();
+    }
+}
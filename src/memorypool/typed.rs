@@ -0,0 +1,287 @@
+use core::{
+    fmt,
+    marker::PhantomData,
+    mem,
+    ops::Deref,
+    ptr::{self, NonNull},
+    sync::atomic::{self, AtomicUsize, Ordering},
+};
+
+use super::{
+    Block, BuildError, GetError, GetTimeoutError, MemoryPool, MemoryPoolRef, PoolBox, TryGetError,
+};
+use crate::{
+    abi,
+    error::{Error, ErrorCode},
+    time::Timeout,
+};
+
+/// An owned memory pool whose memory blocks hold values of type `T`.
+///
+/// The memory blocks are large enough and suitably aligned to hold a `T` as
+/// well as a [`PoolRc`]`<T>`'s reference count.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use itron::memorypool::TypedPool;
+/// let pool = TypedPool::new(4).expect("failed to create a memory pool");
+///
+/// let mut packet = pool.alloc([0u8; 64]).expect("failed to allocate a block");
+/// packet[0] = 0x55;
+/// // The block is released when `packet` is dropped
+///
+/// let shared = pool.alloc_rc([0u8; 64]).expect("failed to allocate a block");
+/// let shared2 = shared.clone();
+/// // The block is released when both `shared` and `shared2` are dropped
+/// ```
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+pub struct TypedPool<T> {
+    pool: MemoryPool,
+    _phantom: PhantomData<T>,
+}
+
+unsafe impl<T> Send for TypedPool<T> {}
+unsafe impl<T> Sync for TypedPool<T> {}
+
+impl<T> fmt::Debug for TypedPool<T> {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.pool.fmt(f)
+    }
+}
+
+impl<T> TypedPool<T> {
+    /// `acre_mpf`: Create a memory pool with `block_count` memory blocks.
+    ///
+    /// Fails with `BadParam` if `T`'s alignment is greater than that of
+    /// memory blocks.
+    pub fn new(block_count: usize) -> Result<Self, Error<BuildError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => {
+                if mem::align_of::<RcBox<T>>() > mem::align_of::<abi::MPF_T>() {
+                    // Safety: `E_PAR` is handled by `BuildError`
+                    // (Warning: This is not true for `cfg(feature = "none")`.)
+                    return Err(unsafe {
+                        Error::new_unchecked(ErrorCode::new_unchecked(abi::E_PAR))
+                    });
+                }
+
+                let pool = MemoryPool::build()
+                    .block_size(mem::size_of::<RcBox<T>>())
+                    .block_count(block_count)
+                    .finish()?;
+
+                Ok(Self {
+                    pool,
+                    _phantom: PhantomData,
+                })
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Allocate a memory block and move `value` into it. Blocks the current
+    /// task if no free memory blocks are available.
+    ///
+    /// `value` is dropped if this method fails.
+    #[inline]
+    pub fn alloc(&self, value: T) -> Result<PoolBox<'_, T>, Error<GetError>> {
+        let block = self.pool.as_ref().get()?;
+        // Safety: `block` was acquired from `self.pool`
+        Ok(unsafe { self.init_box(block, value) })
+    }
+
+    /// [`alloc`](Self::alloc) with timeout.
+    #[inline]
+    pub fn alloc_timeout(
+        &self,
+        value: T,
+        tmo: Timeout,
+    ) -> Result<PoolBox<'_, T>, Error<GetTimeoutError>> {
+        let block = self.pool.as_ref().get_timeout(tmo)?;
+        // Safety: `block` was acquired from `self.pool`
+        Ok(unsafe { self.init_box(block, value) })
+    }
+
+    /// Allocate a memory block and move `value` into it. Fails and returns
+    /// immediately if no free memory blocks are available.
+    ///
+    /// `value` is dropped if this method fails.
+    #[inline]
+    pub fn try_alloc(&self, value: T) -> Result<PoolBox<'_, T>, Error<TryGetError>> {
+        let block = self.pool.as_ref().try_get()?;
+        // Safety: `block` was acquired from `self.pool`
+        Ok(unsafe { self.init_box(block, value) })
+    }
+
+    /// Allocate a memory block and move `value` into it, returning a
+    /// reference-counted pointer. Blocks the current task if no free memory
+    /// blocks are available.
+    ///
+    /// `value` is dropped if this method fails.
+    #[inline]
+    pub fn alloc_rc(&self, value: T) -> Result<PoolRc<'_, T>, Error<GetError>> {
+        let block = self.pool.as_ref().get()?;
+        // Safety: `block` was acquired from `self.pool`
+        Ok(unsafe { self.init_rc(block, value) })
+    }
+
+    /// [`alloc_rc`](Self::alloc_rc) with timeout.
+    #[inline]
+    pub fn alloc_rc_timeout(
+        &self,
+        value: T,
+        tmo: Timeout,
+    ) -> Result<PoolRc<'_, T>, Error<GetTimeoutError>> {
+        let block = self.pool.as_ref().get_timeout(tmo)?;
+        // Safety: `block` was acquired from `self.pool`
+        Ok(unsafe { self.init_rc(block, value) })
+    }
+
+    /// Allocate a memory block and move `value` into it, returning a
+    /// reference-counted pointer. Fails and returns immediately if no free
+    /// memory blocks are available.
+    ///
+    /// `value` is dropped if this method fails.
+    #[inline]
+    pub fn try_alloc_rc(&self, value: T) -> Result<PoolRc<'_, T>, Error<TryGetError>> {
+        let block = self.pool.as_ref().try_get()?;
+        // Safety: `block` was acquired from `self.pool`
+        Ok(unsafe { self.init_rc(block, value) })
+    }
+
+    /// # Safety
+    ///
+    /// `block` must be a memory block acquired from `self.pool`.
+    unsafe fn init_box(&self, block: Block, value: T) -> PoolBox<'_, T> {
+        let ptr = block as *mut T;
+        // Safety: `block` is large enough and suitably aligned for `T`
+        unsafe {
+            ptr.write(value);
+            PoolBox::from_raw(self.pool.as_ref(), NonNull::new_unchecked(ptr))
+        }
+    }
+
+    /// # Safety
+    ///
+    /// `block` must be a memory block acquired from `self.pool`.
+    unsafe fn init_rc(&self, block: Block, value: T) -> PoolRc<'_, T> {
+        let ptr = block as *mut RcBox<T>;
+        // Safety: `block` is large enough and suitably aligned for
+        // `RcBox<T>`
+        unsafe {
+            ptr.write(RcBox {
+                count: AtomicUsize::new(1),
+                value,
+            });
+            PoolRc {
+                ptr: NonNull::new_unchecked(ptr),
+                pool: self.pool.as_ref(),
+                _phantom: PhantomData,
+            }
+        }
+    }
+}
+
+/// The contents of a memory block owned by [`PoolRc`].
+struct RcBox<T> {
+    count: AtomicUsize,
+    value: T,
+}
+
+/// A thread-safe reference-counted pointer to a `T` stored in a memory block
+/// of a [`TypedPool`].
+///
+/// Drops the `T` and [releases] the memory block when the last `PoolRc`
+/// pointing to it is dropped. The destructor will panic if the releasing
+/// fails.
+///
+/// [releases]: MemoryPoolRef::release
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+pub struct PoolRc<'a, T> {
+    ptr: NonNull<RcBox<T>>,
+    pool: MemoryPoolRef<'a>,
+    _phantom: PhantomData<RcBox<T>>,
+}
+
+unsafe impl<T: Send + Sync> Send for PoolRc<'_, T> {}
+unsafe impl<T: Send + Sync> Sync for PoolRc<'_, T> {}
+
+impl<T: fmt::Debug> fmt::Debug for PoolRc<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T> PoolRc<'_, T> {
+    #[inline]
+    fn inner(&self) -> &RcBox<T> {
+        // Safety: The `RcBox` is alive while `self` is alive
+        unsafe { self.ptr.as_ref() }
+    }
+
+    /// Get the number of `PoolRc`s pointing to the same value.
+    #[inline]
+    pub fn strong_count(this: &Self) -> usize {
+        this.inner().count.load(Ordering::Acquire)
+    }
+
+    /// Get a mutable reference to the value if there are no other `PoolRc`s
+    /// pointing to it.
+    #[inline]
+    pub fn get_mut(this: &mut Self) -> Option<&mut T> {
+        if Self::strong_count(this) == 1 {
+            // Safety: `this` is the only reference to the value
+            Some(unsafe { &mut this.ptr.as_mut().value })
+        } else {
+            None
+        }
+    }
+
+    /// Return `true` if the two `PoolRc`s point to the same value.
+    #[inline]
+    pub fn ptr_eq(this: &Self, other: &Self) -> bool {
+        this.ptr == other.ptr
+    }
+}
+
+impl<T> Clone for PoolRc<'_, T> {
+    #[inline]
+    fn clone(&self) -> Self {
+        let old_count = self.inner().count.fetch_add(1, Ordering::Relaxed);
+        // Prevent the count from overflowing even if `PoolRc`s are leaked
+        assert!(old_count <= isize::MAX as usize, "reference count overflow");
+        Self {
+            ptr: self.ptr,
+            pool: self.pool,
+            _phantom: PhantomData,
+        }
+    }
+}
+
+impl<T> Deref for PoolRc<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        &self.inner().value
+    }
+}
+
+impl<T> Drop for PoolRc<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        if self.inner().count.fetch_sub(1, Ordering::Release) != 1 {
+            return;
+        }
+        // Synchronize with the other `PoolRc`s' `fetch_sub`
+        atomic::fence(Ordering::Acquire);
+
+        // Safety: This was the last reference to the `RcBox`
+        unsafe { ptr::drop_in_place(self.ptr.as_ptr()) };
+        self.pool.release(self.ptr.as_ptr() as _).unwrap();
+    }
+}
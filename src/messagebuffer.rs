@@ -815,8 +815,63 @@ mod owned {
         }
     }
 
+    /// A statically allocated storage for a message buffer's queued messages
+    /// (`T_CMBF::mbfmb`) used by a message buffer created by
+    /// [`Builder::storage`]. `N` is the message buffer's capacity, measured in
+    /// bytes.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use itron::messagebuffer::{MessageBuffer, MessageBufferStorage};
+    /// static mut STORAGE: MessageBufferStorage<128> = MessageBufferStorage::new();
+    /// let buffer = MessageBuffer::build()
+    ///     .storage(unsafe { &mut *core::ptr::addr_of_mut!(STORAGE) })
+    ///     .max_message_size(64)
+    ///     .finish()
+    ///     .expect("failed to create a message buffer");
+    /// ```
+    #[repr(C)]
+    #[cfg_attr(
+        feature = "doc_cfg",
+        doc(cfg(all(feature = "dcre", not(feature = "asp3"))))
+    )]
+    pub struct MessageBufferStorage<const N: usize> {
+        /// Aligns the region to `MB_T` and rounds its size up to a multiple
+        /// of `MB_T`'s size.
+        _align: [abi::MB_T; 0],
+        region: MaybeUninit<[u8; N]>,
+    }
+
+    impl<const N: usize> MessageBufferStorage<N> {
+        /// Construct a `MessageBufferStorage`.
+        #[inline]
+        pub const fn new() -> Self {
+            Self {
+                _align: [],
+                region: MaybeUninit::uninit(),
+            }
+        }
+    }
+
+    impl<const N: usize> Default for MessageBufferStorage<N> {
+        #[inline]
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
+    impl<const N: usize> fmt::Debug for MessageBufferStorage<N> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("MessageBufferStorage")
+                .field("capacity", &N)
+                .finish()
+        }
+    }
+
     impl<Capacity, MaxMessageSize> Builder<Capacity, MaxMessageSize> {
-        /// (**Mandatory**) Specify the capacity, measured in bytes.
+        /// (**Mandatory**) Specify the capacity, measured in bytes. The kernel
+        /// allocates storage for queued messages.
         #[inline]
         pub fn capacity(self, value: usize) -> Builder<(), MaxMessageSize> {
             Builder {
@@ -826,11 +881,30 @@ mod owned {
                 #[cfg(not(feature = "none"))]
                 raw: abi::T_CMBF {
                     mbfsz: value,
+                    mbfmb: core::ptr::null_mut(),
                     ..self.raw
                 },
             }
         }
 
+        /// (**Mandatory**) Specify to use the specified storage for queued
+        /// messages. This also sets the capacity to `N`.
+        #[inline]
+        pub fn storage<const N: usize>(
+            self,
+            storage: &'static mut MessageBufferStorage<N>,
+        ) -> Builder<(), MaxMessageSize> {
+            let builder = self.capacity(N);
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CMBF {
+                    mbfmb: storage.region.as_mut_ptr() as *mut u8,
+                    ..builder.raw
+                },
+                ..builder
+            }
+        }
+
         /// (**Mandatory**) Specify the maximum message size, measured in bytes.
         #[inline]
         pub fn max_message_size(self, value: usize) -> Builder<Capacity, ()> {
@@ -0,0 +1,324 @@
+//! Interactive kernel monitor
+//!
+//! [`Monitor`] is a line-oriented command interpreter that runs over any byte
+//! stream, such as a UART. It reads bytes from a user-supplied function and
+//! writes its output to a [`core::fmt::Write`].
+//!
+//! # Commands
+//!
+//! | Command      | Description                                            |
+//! | ------------ | ------------------------------------------------------ |
+//! | `help`       | List the available commands                            |
+//! | `ps`         | List all tasks and their states                        |
+//! | `time`       | Show the current system time                           |
+//! | `sem <id>`   | Show a semaphore's state                               |
+//! | `mtx <id>`   | Show a mutex's state                                   |
+//! | `dtq <id>`   | Show a dataqueue's state                               |
+//! | `mpf <id>`   | Show a fixed-sized memory pool's state                 |
+//! | `act <id>`   | Activate a task ([opt-in][allow_task_control])         |
+//! | `wake <id>`  | Wake up a task ([opt-in][allow_task_control])          |
+//! | `sus <id>`   | Suspend a task ([opt-in][allow_task_control])          |
+//! | `rsm <id>`   | Resume a suspended task ([opt-in][allow_task_control]) |
+//!
+//! [allow_task_control]: Monitor::allow_task_control
+use core::{fmt, mem, str};
+
+use crate::{
+    abi, dataqueue::DataqueueRef, memorypool::MemoryPoolRef, mutex::MutexRef,
+    semaphore::SemaphoreRef, task::TaskRef, time::SystemTime,
+};
+
+/// An interactive kernel monitor. See [the module-level documentation](self)
+/// for the list of commands.
+///
+/// The commands call the kernel's service calls from the calling context, so
+/// the monitor should be run by a task.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use core::fmt;
+/// use itron::monitor::Monitor;
+///
+/// struct Uart;
+///
+/// impl fmt::Write for Uart {
+///     fn write_str(&mut self, s: &str) -> fmt::Result {
+///         // Transmit `s`
+///         Ok(())
+///     }
+/// }
+///
+/// fn read_byte() -> Option<u8> {
+///     // Receive a byte. Return `None` to stop the monitor.
+///     None
+/// }
+///
+/// let mut line = [0u8; 64];
+/// let mut monitor = Monitor::new(&mut line);
+/// monitor.run(&mut Uart, read_byte).unwrap();
+/// ```
+#[derive(Debug)]
+pub struct Monitor<'a> {
+    line: &'a mut [u8],
+    len: usize,
+    last_was_cr: bool,
+    allows_task_control: bool,
+}
+
+impl<'a> Monitor<'a> {
+    /// Construct a `Monitor` that uses `line` to hold a command line. Input
+    /// bytes that don't fit in `line` are discarded.
+    #[inline]
+    pub fn new(line: &'a mut [u8]) -> Self {
+        Self {
+            line,
+            len: 0,
+            last_was_cr: false,
+            allows_task_control: false,
+        }
+    }
+
+    /// Enable the commands that operate on tasks (`act`, `wake`, `sus`, and
+    /// `rsm`).
+    ///
+    /// # Safety
+    ///
+    /// These commands construct [`TaskRef`]s from the task IDs typed by the
+    /// user. The caller must ensure that operating on any task with such an
+    /// ID doesn't violate [the safety requirements of object IDs][1].
+    ///
+    /// [1]: crate#object-id-wrappers
+    #[inline]
+    pub unsafe fn allow_task_control(self) -> Self {
+        Self {
+            allows_task_control: true,
+            ..self
+        }
+    }
+
+    /// Output the prompt and process bytes from `read_byte` until it returns
+    /// `None`.
+    pub fn run(
+        &mut self,
+        out: &mut impl fmt::Write,
+        mut read_byte: impl FnMut() -> Option<u8>,
+    ) -> fmt::Result {
+        self.prompt(out)?;
+        while let Some(byte) = read_byte() {
+            self.push_byte(out, byte)?;
+        }
+        Ok(())
+    }
+
+    /// Output the prompt.
+    #[inline]
+    pub fn prompt(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str("> ")
+    }
+
+    /// Process an input byte. The byte is echoed back to `out`. A line
+    /// terminator (CR, LF, or CR+LF) executes the current line and outputs
+    /// the next prompt.
+    pub fn push_byte(&mut self, out: &mut impl fmt::Write, byte: u8) -> fmt::Result {
+        let last_was_cr = mem::replace(&mut self.last_was_cr, byte == b'\r');
+        match byte {
+            b'\n' if last_was_cr => Ok(()),
+            b'\r' | b'\n' => {
+                out.write_str("\r\n")?;
+                let len = mem::replace(&mut self.len, 0);
+                // `self.line` only contains printable ASCII characters
+                self.execute(out, str::from_utf8(&self.line[..len]).unwrap())?;
+                self.prompt(out)
+            }
+            // Backspace, DEL
+            0x08 | 0x7f => {
+                if self.len > 0 {
+                    self.len -= 1;
+                    out.write_str("\x08 \x08")?;
+                }
+                Ok(())
+            }
+            b' '..=b'~' => {
+                if let Some(slot) = self.line.get_mut(self.len) {
+                    *slot = byte;
+                    self.len += 1;
+                    out.write_char(byte as char)?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Execute a command line.
+    pub fn execute(&self, out: &mut impl fmt::Write, line: &str) -> fmt::Result {
+        let mut args = line.split_ascii_whitespace();
+        let command = match args.next() {
+            Some(command) => command,
+            None => return Ok(()),
+        };
+
+        match command {
+            "help" => self.help(out),
+            "ps" => ps(out),
+            "time" => match SystemTime::now() {
+                Ok(time) => write!(out, "{} us\r\n", time.as_raw()),
+                Err(e) => write!(out, "error: {:?}\r\n", e),
+            },
+            "sem" | "mtx" | "dtq" | "mpf" => {
+                let id = match parse_id(args.next()) {
+                    Some(id) => id,
+                    None => return write!(out, "usage: {} <id>\r\n", command),
+                };
+                // Safety: We only query the object's state
+                match command {
+                    "sem" => sem(out, unsafe { SemaphoreRef::from_raw_nonnull(id) }),
+                    "mtx" => mtx(out, unsafe { MutexRef::from_raw_nonnull(id) }),
+                    "dtq" => dtq(out, unsafe { DataqueueRef::from_raw_nonnull(id) }),
+                    "mpf" => mpf(out, unsafe { MemoryPoolRef::from_raw_nonnull(id) }),
+                    _ => unreachable!(),
+                }
+            }
+            "act" | "wake" | "sus" | "rsm" if self.allows_task_control => {
+                let id = match parse_id(args.next()) {
+                    Some(id) => id,
+                    None => return write!(out, "usage: {} <id>\r\n", command),
+                };
+                // Safety: The user of `allow_task_control` takes the
+                // responsibility
+                let task = unsafe { TaskRef::from_raw_nonnull(id) };
+                let result = match command {
+                    "act" => task.activate().map_err(|e| e.code()),
+                    "wake" => task.wake().map_err(|e| e.code()),
+                    "sus" => task.suspend().map_err(|e| e.code()),
+                    "rsm" => task.resume().map_err(|e| e.code()),
+                    _ => unreachable!(),
+                };
+                match result {
+                    Ok(()) => Ok(()),
+                    Err(e) => write!(out, "error: {:?}\r\n", e),
+                }
+            }
+            _ => write!(out, "unknown command: {}\r\n", command),
+        }
+    }
+
+    fn help(&self, out: &mut impl fmt::Write) -> fmt::Result {
+        out.write_str(
+            "help        list the commands\r\n\
+             ps          list the tasks\r\n\
+             time        show the system time\r\n\
+             sem <id>    show a semaphore\r\n\
+             mtx <id>    show a mutex\r\n\
+             dtq <id>    show a dataqueue\r\n\
+             mpf <id>    show a memory pool\r\n",
+        )?;
+        if self.allows_task_control {
+            out.write_str(
+                "act <id>    activate a task\r\n\
+                 wake <id>   wake up a task\r\n\
+                 sus <id>    suspend a task\r\n\
+                 rsm <id>    resume a task\r\n",
+            )?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_id(arg: Option<&str>) -> Option<abi::NonNullID> {
+    abi::NonNullID::new(arg?.parse().ok()?)
+}
+
+/// Formats `Some(x)` as `x`'s `Debug` representation and `None` as `-`.
+struct OrDash<T>(Option<T>);
+
+impl<T: fmt::Debug> fmt::Display for OrDash<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.0 {
+            Some(x) => fmt::Debug::fmt(x, f),
+            None => f.write_str("-"),
+        }
+    }
+}
+
+fn ps(out: &mut impl fmt::Write) -> fmt::Result {
+    out.write_str("  ID PRI BPRI STATE\r\n")?;
+    let mut id = abi::NonNullID::new(1).unwrap();
+    loop {
+        // Safety: We only query the task's state
+        match unsafe { TaskRef::from_raw_nonnull(id) }.info() {
+            Ok(info) => {
+                write!(
+                    out,
+                    "{:4} {:3} {:4} {:?}",
+                    id,
+                    info.current_priority(),
+                    info.base_priority(),
+                    info.state(),
+                )?;
+                if let Some(cause) = info.wait_cause() {
+                    write!(out, " ({:?})", cause)?;
+                }
+                out.write_str("\r\n")?;
+            }
+            Err(e) if e.code().get() == abi::E_ID => return Ok(()),
+            Err(e) if e.code().get() == abi::E_NOEXS => {}
+            Err(e) => return write!(out, "error: {:?}\r\n", e),
+        }
+
+        id = match id.get().checked_add(1).and_then(abi::NonNullID::new) {
+            Some(id) => id,
+            None => return Ok(()),
+        };
+    }
+}
+
+fn sem(out: &mut impl fmt::Write, semaphore: SemaphoreRef<'_>) -> fmt::Result {
+    match semaphore.info() {
+        Ok(info) => write!(
+            out,
+            "count: {}, waiting: {}\r\n",
+            info.count(),
+            OrDash(info.first_waiting_task_id()),
+        ),
+        Err(e) => write!(out, "error: {:?}\r\n", e),
+    }
+}
+
+fn mtx(out: &mut impl fmt::Write, mutex: MutexRef<'_>) -> fmt::Result {
+    match mutex.info() {
+        Ok(info) => write!(
+            out,
+            "owner: {}, waiting: {}\r\n",
+            OrDash(info.owning_task_id()),
+            OrDash(info.first_waiting_task_id()),
+        ),
+        Err(e) => write!(out, "error: {:?}\r\n", e),
+    }
+}
+
+fn dtq(out: &mut impl fmt::Write, dataqueue: DataqueueRef<'_>) -> fmt::Result {
+    match dataqueue.info() {
+        Ok(info) => write!(
+            out,
+            "len: {}, waiting to send: {}, waiting to receive: {}\r\n",
+            info.len(),
+            OrDash(info.first_waiting_sending_task_id()),
+            OrDash(info.first_waiting_receiving_task_id()),
+        ),
+        Err(e) => write!(out, "error: {:?}\r\n", e),
+    }
+}
+
+fn mpf(out: &mut impl fmt::Write, memory_pool: MemoryPoolRef<'_>) -> fmt::Result {
+    match memory_pool.info() {
+        Ok(info) => write!(
+            out,
+            "free blocks: {}, waiting: {}\r\n",
+            info.free_block_count(),
+            OrDash(info.first_waiting_task_id()),
+        ),
+        Err(e) => write!(out, "error: {:?}\r\n", e),
+    }
+}
@@ -1,11 +1,920 @@
-//! Priority dataqueues (TODO)
-// TODO: acre_pdq
-// TODO: del_pdq
-// TODO: snd_pdq
-// TODO: psnd_pdq
-// TODO: tsnd_pdq
-// TODO: rcv_pdq
-// TODO: prcv_pdq
-// TODO: trcv_pdq
-// TODO: ini_pdq
-// TODO: ref_pdq
+//! Priority dataqueues
+
+use core::{fmt, marker::PhantomData, mem::MaybeUninit};
+
+use crate::{
+    abi,
+    error::{Error, ErrorCode, ErrorKind, Kind},
+    time::Timeout,
+};
+
+define_error_kind! {
+    /// Error type for [`PriorityDataqueueRef::send`].
+    pub enum SendError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        /// The task is a restricted task.
+        #[cfg(all(not(feature = "none"), feature = "rstr_task"))]
+        NotSupported,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The data priority is out of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        #[cfg(not(feature = "none"))]
+        Released,
+        #[cfg(not(feature = "none"))]
+        TerminateRequest,
+        #[cfg(all(not(feature = "none"), feature = "dcre"))]
+        Deleted,
+    }
+}
+
+impl ErrorKind for SendError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(all(not(feature = "none"), feature = "rstr_task"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_RLWAI => Some(Self::Released(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_RASTER => Some(Self::TerminateRequest(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(all(not(feature = "none"), feature = "dcre"))]
+            abi::E_DLT => Some(Self::Deleted(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`PriorityDataqueueRef::send_timeout`].
+    pub enum SendTimeoutError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        /// The task is a restricted task.
+        #[cfg(all(not(feature = "none"), feature = "rstr_task"))]
+        NotSupported,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The data priority is out of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        #[cfg(not(feature = "none"))]
+        Timeout,
+        #[cfg(not(feature = "none"))]
+        Released,
+        #[cfg(not(feature = "none"))]
+        TerminateRequest,
+        #[cfg(all(not(feature = "none"), feature = "dcre"))]
+        Deleted,
+    }
+}
+
+impl ErrorKind for SendTimeoutError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            // `E_PAR` caused by an invalid timeout value is considered
+            // critical, so it's safe to assume it's caused by an invalid data
+            // priority
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(all(not(feature = "none"), feature = "rstr_task"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_TMOUT => Some(Self::Timeout(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_RLWAI => Some(Self::Released(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_RASTER => Some(Self::TerminateRequest(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(all(not(feature = "none"), feature = "dcre"))]
+            abi::E_DLT => Some(Self::Deleted(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`PriorityDataqueueRef::try_send`].
+    pub enum TrySendError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The data priority is out of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        #[cfg(not(feature = "none"))]
+        Timeout,
+    }
+}
+
+impl ErrorKind for TrySendError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_TMOUT => Some(Self::Timeout(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`PriorityDataqueueRef::recv`].
+    pub enum RecvError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        /// The task is a restricted task.
+        #[cfg(all(not(feature = "none"), feature = "rstr_task"))]
+        NotSupported,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        #[cfg(not(feature = "none"))]
+        Released,
+        #[cfg(not(feature = "none"))]
+        TerminateRequest,
+        #[cfg(all(not(feature = "none"), feature = "dcre"))]
+        Deleted,
+    }
+}
+
+impl ErrorKind for RecvError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(all(not(feature = "none"), feature = "rstr_task"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_RLWAI => Some(Self::Released(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_RASTER => Some(Self::TerminateRequest(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(all(not(feature = "none"), feature = "dcre"))]
+            abi::E_DLT => Some(Self::Deleted(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`PriorityDataqueueRef::recv_timeout`].
+    pub enum RecvTimeoutError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        /// The task is a restricted task.
+        #[cfg(all(not(feature = "none"), feature = "rstr_task"))]
+        NotSupported,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        #[cfg(not(feature = "none"))]
+        Timeout,
+        #[cfg(not(feature = "none"))]
+        Released,
+        #[cfg(not(feature = "none"))]
+        TerminateRequest,
+        #[cfg(all(not(feature = "none"), feature = "dcre"))]
+        Deleted,
+    }
+}
+
+impl ErrorKind for RecvTimeoutError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            // E_PAR is considered critial, hence excluded
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(all(not(feature = "none"), feature = "rstr_task"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_TMOUT => Some(Self::Timeout(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_RLWAI => Some(Self::Released(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_RASTER => Some(Self::TerminateRequest(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(all(not(feature = "none"), feature = "dcre"))]
+            abi::E_DLT => Some(Self::Deleted(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`PriorityDataqueueRef::try_recv`].
+    pub enum TryRecvError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        #[cfg(not(feature = "none"))]
+        Timeout,
+    }
+}
+
+impl ErrorKind for TryRecvError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_TMOUT => Some(Self::Timeout(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`PriorityDataqueueRef::initialize`].
+    pub enum InitializeError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for InitializeError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`PriorityDataqueueRef::info`].
+    pub enum InfoError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for InfoError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`PriorityDataqueue::build`].
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub enum BuildError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// Ran out of memory or priority dataqueue IDs, or the specified
+        /// capacity does not fit in `uint_t`.
+        #[cfg(not(feature = "none"))]
+        OutOfMemory,
+        /// Bad parameter.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+#[cfg(feature = "dcre")]
+impl ErrorKind for BuildError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            // `E_MACV` is considered critical, hence excluded
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOID | abi::E_NOMEM => Some(Self::OutOfMemory(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR | abi::E_RSATR => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`PriorityDataqueueRef::delete`].
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub enum DeleteError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+#[cfg(feature = "dcre")]
+impl ErrorKind for DeleteError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+pub use crate::dataqueue::DataElement;
+
+/// The priority of a data element sent through a priority dataqueue. A
+/// smaller value represents a higher priority.
+pub type Priority = abi::PRI;
+
+/// Priority dataqueue information returned by [`PriorityDataqueueRef::info`].
+#[derive(Debug, Clone, Copy)]
+pub struct Info {
+    #[cfg(not(feature = "none"))]
+    raw: abi::T_RPDQ,
+}
+
+impl Info {
+    /// Get the number of data items contained in the priority dataqueue.
+    #[inline]
+    pub fn len(&self) -> usize {
+        match () {
+            // Since `spdqcnt` represents a number of objects in memory, the
+            // conversion should not cause an overflow
+            #[cfg(not(feature = "none"))]
+            () => self.raw.spdqcnt as usize,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get a flag indicating whether the priority dataqueue is empty.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Get the first waiting sender's task ID.
+    #[inline]
+    pub fn first_waiting_sending_task_id(&self) -> Option<abi::NonNullID> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => abi::NonNullID::new(self.raw.stskid),
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the first waiting receiver's task ID.
+    #[inline]
+    pub fn first_waiting_receiving_task_id(&self) -> Option<abi::NonNullID> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => abi::NonNullID::new(self.raw.rtskid),
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// A borrowed reference to a priority dataqueue.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct PriorityDataqueueRef<'a> {
+    id: abi::NonNullID,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl fmt::Debug for PriorityDataqueueRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "PriorityDataqueue({})", self.id)
+    }
+}
+
+/// # Object ID conversion
+impl PriorityDataqueueRef<'_> {
+    /// Construct a `PriorityDataqueueRef` from a raw object ID.
+    ///
+    /// # Safety
+    ///
+    /// See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
+        Self {
+            id,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get the raw object ID.
+    #[inline]
+    pub const fn as_raw(self) -> abi::ID {
+        self.id.get()
+    }
+
+    /// Get the raw object ID as [` abi::NonNullID`].
+    #[inline]
+    pub const fn as_raw_nonnull(self) -> abi::NonNullID {
+        self.id
+    }
+}
+
+/// # Management
+impl PriorityDataqueueRef<'_> {
+    /// `del_pdq`: Delete the priority dataqueue.
+    ///
+    /// # Safety
+    ///
+    /// See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    #[doc(alias = "del_pdq")]
+    #[cfg(feature = "dcre")]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub unsafe fn delete(self) -> Result<(), Error<DeleteError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::del_pdq(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `ref_pdq`: Get the priority dataqueue's general information.
+    #[inline]
+    #[doc(alias = "ref_pdq")]
+    pub fn info(self) -> Result<Info, Error<InfoError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut pri = MaybeUninit::uninit();
+                Error::err_if_negative(abi::ref_pdq(self.as_raw(), pri.as_mut_ptr()))?;
+                Ok(Info {
+                    raw: pri.assume_init(),
+                })
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// # Priority Dataqueue Operations
+impl PriorityDataqueueRef<'_> {
+    /// `snd_pdq`: Send a data element to the priority dataqueue. Blocks the
+    /// current task if the priority dataqueue is full.
+    #[inline]
+    #[doc(alias = "snd_pdq")]
+    pub fn send(
+        self,
+        data_element: DataElement,
+        priority: Priority,
+    ) -> Result<(), Error<SendError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::snd_pdq(
+                    self.as_raw(),
+                    data_element as isize,
+                    priority,
+                ))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `tsnd_pdq`: Send a data element to the priority dataqueue. Blocks the
+    /// current task with timeout if the priority dataqueue is full.
+    #[inline]
+    #[doc(alias = "tsnd_pdq")]
+    pub fn send_timeout(
+        self,
+        data_element: DataElement,
+        priority: Priority,
+        tmo: Timeout,
+    ) -> Result<(), Error<SendTimeoutError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::tsnd_pdq(
+                    self.as_raw(),
+                    data_element as isize,
+                    priority,
+                    tmo.as_raw(),
+                ))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `psnd_pdq`: Send a data element to the priority dataqueue. Fails and
+    /// returns an error if the priority dataqueue is full.
+    #[inline]
+    #[doc(alias = "psnd_pdq")]
+    pub fn try_send(
+        self,
+        data_element: DataElement,
+        priority: Priority,
+    ) -> Result<(), Error<TrySendError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::psnd_pdq(
+                    self.as_raw(),
+                    data_element as isize,
+                    priority,
+                ))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `rcv_pdq`: Receive a data element from the priority dataqueue. Blocks
+    /// the current task if the priority dataqueue is empty.
+    ///
+    /// Returns the received data element and its priority.
+    #[inline]
+    #[doc(alias = "rcv_pdq")]
+    pub fn recv(self) -> Result<(DataElement, Priority), Error<RecvError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut out = MaybeUninit::<isize>::uninit();
+                let mut pri = MaybeUninit::uninit();
+                Error::err_if_negative(abi::rcv_pdq(
+                    self.as_raw(),
+                    out.as_mut_ptr(),
+                    pri.as_mut_ptr(),
+                ))?;
+                Ok((out.assume_init() as usize, pri.assume_init()))
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `trcv_pdq`: Receive a data element from the priority dataqueue. Blocks
+    /// the current task with timeout if the priority dataqueue is empty.
+    ///
+    /// Returns the received data element and its priority.
+    #[inline]
+    #[doc(alias = "trcv_pdq")]
+    pub fn recv_timeout(
+        self,
+        tmo: Timeout,
+    ) -> Result<(DataElement, Priority), Error<RecvTimeoutError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut out = MaybeUninit::<isize>::uninit();
+                let mut pri = MaybeUninit::uninit();
+                Error::err_if_negative(abi::trcv_pdq(
+                    self.as_raw(),
+                    out.as_mut_ptr(),
+                    pri.as_mut_ptr(),
+                    tmo.as_raw(),
+                ))?;
+                Ok((out.assume_init() as usize, pri.assume_init()))
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `prcv_pdq`: Receive a data element from the priority dataqueue. Fails
+    /// and returns an error if the priority dataqueue is empty.
+    ///
+    /// Returns the received data element and its priority.
+    #[inline]
+    #[doc(alias = "prcv_pdq")]
+    pub fn try_recv(self) -> Result<(DataElement, Priority), Error<TryRecvError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut out = MaybeUninit::<isize>::uninit();
+                let mut pri = MaybeUninit::uninit();
+                Error::err_if_negative(abi::prcv_pdq(
+                    self.as_raw(),
+                    out.as_mut_ptr(),
+                    pri.as_mut_ptr(),
+                ))?;
+                Ok((out.assume_init() as usize, pri.assume_init()))
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `ini_pdq`: Initialize the priority dataqueue.
+    #[inline]
+    #[doc(alias = "ini_pdq")]
+    pub fn initialize(self) -> Result<(), Error<InitializeError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::ini_pdq(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+#[cfg(feature = "dcre")]
+pub use self::owned::*;
+
+#[cfg(feature = "dcre")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+mod owned {
+    use super::*;
+    use crate::wait::QueueOrder;
+    use core::convert::TryInto;
+
+    /// The builder type for [priority dataqueues](PriorityDataqueue).
+    /// Created by [`PriorityDataqueue::build`].
+    ///
+    /// Its generic parameters are an implementation detail.
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    #[must_use = "`Builder` creates nothing unless you call `.finish()`"]
+    pub struct Builder<Capacity, MaxDataPriority> {
+        #[allow(dead_code)]
+        capacity: Capacity,
+        #[allow(dead_code)]
+        max_data_priority: MaxDataPriority,
+        capacity_overflow: bool,
+        #[cfg(not(feature = "none"))]
+        raw: abi::T_CPDQ,
+    }
+
+    /// Builder field hole types
+    #[allow(non_camel_case_types)]
+    #[doc(hidden)]
+    pub mod builder_hole {
+        pub struct __capacity_is_not_specified__;
+        pub struct __max_data_priority_is_not_specified__;
+    }
+
+    impl PriorityDataqueue {
+        /// `acre_pdq`: Create a builder for `PriorityDataqueue`.
+        ///
+        /// # Examples
+        ///
+        /// ```rust,no_run
+        /// use itron::prioritydataqueue::PriorityDataqueue;
+        /// let queue = PriorityDataqueue::build()
+        ///     .capacity(2)
+        ///     .max_data_priority(4)
+        ///     .finish()
+        ///     .expect("failed to create a priority dataqueue");
+        ///
+        /// queue.as_ref().send(1, 4)
+        ///    .expect("failed to send a data element");
+        /// queue.as_ref().send(2, 1)
+        ///    .expect("failed to send a data element");
+        /// queue.as_ref().try_send(3, 1)
+        ///    .expect_err("unexpectedly succeeded to send a data element");
+        ///
+        /// assert_eq!(queue.as_ref().recv().unwrap(), (2, 1));
+        /// assert_eq!(queue.as_ref().recv().unwrap(), (1, 4));
+        /// ```
+        #[inline]
+        #[doc(alias = "acre_pdq")]
+        pub fn build() -> Builder<
+            builder_hole::__capacity_is_not_specified__,
+            builder_hole::__max_data_priority_is_not_specified__,
+        > {
+            Builder {
+                capacity: builder_hole::__capacity_is_not_specified__,
+                max_data_priority: builder_hole::__max_data_priority_is_not_specified__,
+                capacity_overflow: false,
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CPDQ {
+                    pdqatr: abi::TA_NULL,
+                    pdqcnt: 0,
+                    maxdpri: 0,
+                    pdqmb: core::ptr::null_mut(),
+                },
+            }
+        }
+    }
+
+    impl<Capacity, MaxDataPriority> Builder<Capacity, MaxDataPriority> {
+        /// (**Mandatory**) Specify the priority dataqueue's capacity, measured
+        /// in number of data elements.
+        #[inline]
+        pub fn capacity(self, value: usize) -> Builder<(), MaxDataPriority> {
+            let (capacity, capacity_overflow) = match value.try_into() {
+                Ok(x) => (x, false),
+                Err(_) => (0, true),
+            };
+            Builder {
+                capacity: (),
+                max_data_priority: self.max_data_priority,
+                capacity_overflow,
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CPDQ {
+                    pdqcnt: capacity,
+                    ..self.raw
+                },
+            }
+        }
+
+        /// (**Mandatory**) Specify the lowest data priority (i.e., the
+        /// maximum numeric value) that can be sent to the priority dataqueue.
+        #[inline]
+        pub fn max_data_priority(self, value: Priority) -> Builder<Capacity, ()> {
+            Builder {
+                capacity: self.capacity,
+                max_data_priority: (),
+                capacity_overflow: self.capacity_overflow,
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CPDQ {
+                    maxdpri: value,
+                    ..self.raw
+                },
+            }
+        }
+
+        /// Specify the queue order of the send wait queue. Defaults to `Fifo`
+        /// when unspecified.
+        #[inline]
+        pub fn queue_order(self, value: QueueOrder) -> Self {
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CPDQ {
+                    pdqatr: value.as_raw_atr(),
+                    ..self.raw
+                },
+                ..self
+            }
+        }
+    }
+
+    impl Builder<(), ()> {
+        /// Create a priority dataqueue using the specified parameters.
+        #[allow(unused_mut)]
+        pub fn finish(mut self) -> Result<PriorityDataqueue, Error<BuildError>> {
+            match () {
+                #[cfg(not(feature = "none"))]
+                () => unsafe {
+                    if self.capacity_overflow {
+                        // Safety: `E_NOMEM` is handled by `BuildError`
+                        // (Warning: This is not true for `cfg(feature = "none")`.)
+                        return Err(Error::new_unchecked(ErrorCode::new_unchecked(abi::E_NOMEM)));
+                    }
+
+                    let id = Error::err_if_negative(abi::acre_pdq(&self.raw))?;
+                    // Safety: We own the priority dataqueue we create
+                    Ok(PriorityDataqueue::from_raw_nonnull(
+                        abi::NonNullID::new_unchecked(id),
+                    ))
+                },
+                #[cfg(feature = "none")]
+                () => unimplemented!(),
+            }
+        }
+    }
+
+    /// An owned priority dataqueue.
+    ///
+    /// [Deletes] the priority dataqueue automatically when dropped. The
+    /// destructor will panic if the deletion fails.
+    ///
+    /// [Deletes]: PriorityDataqueueRef::delete
+    #[derive(PartialEq, Eq)]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub struct PriorityDataqueue(PriorityDataqueueRef<'static>);
+
+    impl fmt::Debug for PriorityDataqueue {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    impl Drop for PriorityDataqueue {
+        #[inline]
+        fn drop(&mut self) {
+            unsafe { self.0.delete().unwrap() };
+        }
+    }
+
+    impl PriorityDataqueue {
+        /// Construct a `PriorityDataqueue` from a raw object ID.
+        ///
+        /// # Safety
+        ///
+        /// See [Object ID Wrappers](crate#object-id-wrappers).
+        #[inline]
+        pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
+            Self(unsafe { PriorityDataqueueRef::from_raw_nonnull(id) })
+        }
+
+        /// Consume and "leak" `self`, returning a reference
+        /// `PriorityDataqueueRef<'a>`.
+        #[inline]
+        pub const fn leak<'a>(self) -> PriorityDataqueueRef<'a> {
+            let out = self.0;
+            core::mem::forget(self);
+            out
+        }
+
+        /// Get the raw object ID.
+        #[inline]
+        pub const fn as_raw(&self) -> abi::ID {
+            self.0.as_raw()
+        }
+
+        /// Get the raw object ID as [` abi::NonNullID`].
+        #[inline]
+        pub const fn as_raw_nonnull(&self) -> abi::NonNullID {
+            self.0.as_raw_nonnull()
+        }
+
+        /// Borrow `PriorityDataqueue` as [`PriorityDataqueueRef`].
+        ///
+        /// Use this to perform operations on priority dataqueues because most
+        /// of the methods are implemented on `PriorityDataqueueRef` but not
+        /// `PriorityDataqueue`.
+        #[inline]
+        pub const fn as_ref(&self) -> PriorityDataqueueRef<'_> {
+            self.0
+        }
+    }
+}
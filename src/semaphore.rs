@@ -477,6 +477,84 @@ impl SemaphoreRef<'_> {
     }
 }
 
+/// # Permits
+impl<'a> SemaphoreRef<'a> {
+    /// [`wait`](Self::wait) and return a [`Permit`] that signals the
+    /// semaphore when dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use itron::{abi, semaphore::SemaphoreRef};
+    /// let flash_writers = unsafe {
+    ///     SemaphoreRef::from_raw_nonnull(abi::NonNullID::new(1).unwrap())
+    /// };
+    ///
+    /// let permit = flash_writers.acquire()
+    ///     .expect("failed to acquire a permit");
+    /// // Write to the flash memory. The permit is released even if we
+    /// // bail out early.
+    /// drop(permit);
+    /// ```
+    #[inline]
+    pub fn acquire(self) -> Result<Permit<'a>, Error<WaitError>> {
+        self.wait()?;
+        Ok(Permit { semaphore: self })
+    }
+
+    /// [`wait_timeout`](Self::wait_timeout) and return a [`Permit`] that
+    /// signals the semaphore when dropped.
+    #[inline]
+    pub fn acquire_timeout(self, tmo: Timeout) -> Result<Permit<'a>, Error<WaitTimeoutError>> {
+        self.wait_timeout(tmo)?;
+        Ok(Permit { semaphore: self })
+    }
+
+    /// [`poll`](Self::poll) and return a [`Permit`] that signals the
+    /// semaphore when dropped.
+    #[inline]
+    pub fn try_acquire(self) -> Result<Permit<'a>, Error<PollError>> {
+        self.poll()?;
+        Ok(Permit { semaphore: self })
+    }
+}
+
+/// An RAII guard representing a unit of a semaphore's count, returned by
+/// [`SemaphoreRef::acquire`], [`SemaphoreRef::acquire_timeout`], and
+/// [`SemaphoreRef::try_acquire`].
+///
+/// [Signals] the semaphore when dropped. The destructor will panic if the
+/// signaling fails.
+///
+/// [Signals]: SemaphoreRef::signal
+#[derive(Debug)]
+#[must_use = "the permit is released immediately if unused"]
+pub struct Permit<'a> {
+    semaphore: SemaphoreRef<'a>,
+}
+
+impl<'a> Permit<'a> {
+    /// Get the semaphore the permit was acquired from.
+    #[inline]
+    pub fn semaphore(&self) -> SemaphoreRef<'a> {
+        self.semaphore
+    }
+
+    /// Consume the permit without signaling the semaphore, permanently
+    /// decreasing the semaphore's count by one.
+    #[inline]
+    pub fn forget(self) {
+        core::mem::forget(self);
+    }
+}
+
+impl Drop for Permit<'_> {
+    #[inline]
+    fn drop(&mut self) {
+        self.semaphore.signal().unwrap();
+    }
+}
+
 #[cfg(feature = "dcre")]
 pub use self::owned::*;
 
@@ -0,0 +1,653 @@
+//! Spinlocks
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    marker::PhantomData,
+    mem::MaybeUninit,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    abi,
+    error::{Error, ErrorCode, ErrorKind, Kind},
+};
+
+define_error_kind! {
+    /// Error type for [`SpinlockRef::lock`].
+    pub enum LockError {
+        /// The current processor already holds a spinlock.
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The current processor already holds the spinlock.
+        #[cfg(not(feature = "none"))]
+        Deadlock,
+    }
+}
+
+impl ErrorKind for LockError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ILUSE => Some(Self::Deadlock(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`SpinlockRef::try_lock`].
+    pub enum TryLockError {
+        /// The current processor already holds a spinlock.
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The current processor already holds the spinlock.
+        #[cfg(not(feature = "none"))]
+        Deadlock,
+        /// The spinlock is held by another processor.
+        #[cfg(not(feature = "none"))]
+        Busy,
+    }
+}
+
+impl ErrorKind for TryLockError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ILUSE => Some(Self::Deadlock(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::Busy(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`SpinlockRef::unlock`].
+    pub enum UnlockError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The spinlock is not currently held by the current processor.
+        #[cfg(not(feature = "none"))]
+        BadSequence,
+    }
+}
+
+impl ErrorKind for UnlockError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ILUSE => Some(Self::BadSequence(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`SpinlockRef::info`].
+    pub enum InfoError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for InfoError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`Spinlock::build`].
+    #[cfg(any(all(feature = "solid_fmp3", feature = "dcre"), feature = "none"))]
+    #[cfg_attr(
+        feature = "doc_cfg",
+        doc(cfg(all(feature = "solid_fmp3", feature = "dcre")))
+    )]
+    pub enum BuildError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// Ran out of memory or spinlock IDs.
+        #[cfg(not(feature = "none"))]
+        OutOfMemory,
+        /// Bad parameter.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+#[cfg(any(all(feature = "solid_fmp3", feature = "dcre"), feature = "none"))]
+impl ErrorKind for BuildError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            // `E_MACV` is considered critical, hence excluded
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOID | abi::E_NOMEM => Some(Self::OutOfMemory(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR | abi::E_RSATR => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`SpinlockRef::delete`].
+    #[cfg(any(all(feature = "solid_fmp3", feature = "dcre"), feature = "none"))]
+    #[cfg_attr(
+        feature = "doc_cfg",
+        doc(cfg(all(feature = "solid_fmp3", feature = "dcre")))
+    )]
+    pub enum DeleteError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// The spinlock is currently held.
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+#[cfg(any(all(feature = "solid_fmp3", feature = "dcre"), feature = "none"))]
+impl ErrorKind for DeleteError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+/// Spinlock information returned by [`SpinlockRef::info`].
+#[derive(Debug, Clone, Copy)]
+pub struct Info {
+    #[cfg(not(feature = "none"))]
+    raw: abi::T_RSPN,
+}
+
+impl Info {
+    /// Get a flag indicating whether the spinlock is currently held by any
+    /// processor.
+    #[inline]
+    pub fn is_locked(&self) -> bool {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => self.raw.spnstat == abi::TSPN_LOC,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// A borrowed reference to a spinlock.
+#[derive(PartialEq, Eq, Clone, Copy)]
+pub struct SpinlockRef<'a> {
+    id: abi::NonNullID,
+    _phantom: PhantomData<&'a ()>,
+}
+
+impl fmt::Debug for SpinlockRef<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Spinlock({})", self.id)
+    }
+}
+
+/// # Object ID conversion
+impl SpinlockRef<'_> {
+    /// Construct a `SpinlockRef` from a raw object ID.
+    ///
+    /// # Safety
+    ///
+    /// See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
+        Self {
+            id,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get the raw object ID.
+    #[inline]
+    pub const fn as_raw(self) -> abi::ID {
+        self.id.get()
+    }
+
+    /// Get the raw object ID as [` abi::NonNullID`].
+    #[inline]
+    pub const fn as_raw_nonnull(self) -> abi::NonNullID {
+        self.id
+    }
+}
+
+/// # Management
+impl SpinlockRef<'_> {
+    /// `del_spn`: Delete the spinlock.
+    ///
+    /// # Safety
+    ///
+    /// See [Object ID Wrappers](crate#object-id-wrappers).
+    #[inline]
+    #[doc(alias = "del_spn")]
+    #[cfg(any(all(feature = "solid_fmp3", feature = "dcre"), feature = "none"))]
+    #[cfg_attr(
+        feature = "doc_cfg",
+        doc(cfg(all(feature = "solid_fmp3", feature = "dcre")))
+    )]
+    pub unsafe fn delete(self) -> Result<(), Error<DeleteError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::del_spn(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `ref_spn`: Get the spinlock's general information.
+    #[inline]
+    #[doc(alias = "ref_spn")]
+    pub fn info(self) -> Result<Info, Error<InfoError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut pri = MaybeUninit::uninit();
+                Error::err_if_negative(abi::ref_spn(self.as_raw(), pri.as_mut_ptr()))?;
+                Ok(Info {
+                    raw: pri.assume_init(),
+                })
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// # Spinlock Operations
+impl SpinlockRef<'_> {
+    /// `loc_spn`: Acquire the spinlock, spinning until it becomes available.
+    /// The current processor enters the CPU lock state on success.
+    #[inline]
+    #[doc(alias = "loc_spn")]
+    pub fn lock(self) -> Result<(), Error<LockError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::loc_spn(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `try_spn`: Attempt to acquire the spinlock. Fails and returns
+    /// immediately if it's held by another processor.
+    #[inline]
+    #[doc(alias = "try_spn")]
+    pub fn try_lock(self) -> Result<(), Error<TryLockError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::try_spn(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `unl_spn`: Release the spinlock. The current processor leaves the CPU
+    /// lock state on success.
+    #[inline]
+    #[doc(alias = "unl_spn")]
+    pub fn unlock(self) -> Result<(), Error<UnlockError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::unl_spn(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
+/// A mutual exclusion primitive protecting data shared between processors,
+/// backed by a spinlock.
+///
+/// The spinlock is held while a [`SpinMutexGuard`] is alive, during which
+/// the current processor is in the CPU lock state. Keep critical sections
+/// short.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use itron::{abi, spinlock::{SpinlockRef, SpinMutex}};
+///
+/// static COUNTER: SpinMutex<'static, u32> = unsafe {
+///     SpinMutex::new(
+///         SpinlockRef::from_raw_nonnull(abi::NonNullID::new_unchecked(1)),
+///         0,
+///     )
+/// };
+///
+/// *COUNTER.lock().expect("failed to lock the spinlock") += 1;
+/// ```
+pub struct SpinMutex<'a, T: ?Sized> {
+    spinlock: SpinlockRef<'a>,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for SpinMutex<'_, T> {}
+unsafe impl<T: ?Sized + Send> Sync for SpinMutex<'_, T> {}
+
+impl<T: ?Sized> fmt::Debug for SpinMutex<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SpinMutex")
+            .field("spinlock", &self.spinlock)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> SpinMutex<'a, T> {
+    /// Construct a `SpinMutex` protecting `value` with `spinlock`.
+    ///
+    /// # Safety
+    ///
+    /// While a [`SpinMutexGuard`] is alive, `spinlock` must not be released
+    /// by anything but the guard (e.g., by calling [`SpinlockRef::unlock`]
+    /// directly).
+    #[inline]
+    pub const unsafe fn new(spinlock: SpinlockRef<'a>, value: T) -> Self {
+        Self {
+            spinlock,
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    /// Consume `self`, returning the protected value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<'a, T: ?Sized> SpinMutex<'a, T> {
+    /// Get the underlying spinlock.
+    #[inline]
+    pub const fn spinlock(&self) -> SpinlockRef<'a> {
+        self.spinlock
+    }
+
+    /// Acquire the spinlock, spinning until it becomes available, and return
+    /// a guard granting access to the protected value.
+    #[inline]
+    pub fn lock(&self) -> Result<SpinMutexGuard<'_, T>, Error<LockError>> {
+        self.spinlock.lock()?;
+        Ok(SpinMutexGuard {
+            mutex: self,
+            _no_send: PhantomData,
+        })
+    }
+
+    /// Attempt to acquire the spinlock and return a guard granting access to
+    /// the protected value. Fails and returns immediately if the spinlock is
+    /// held by another processor.
+    #[inline]
+    pub fn try_lock(&self) -> Result<SpinMutexGuard<'_, T>, Error<TryLockError>> {
+        self.spinlock.try_lock()?;
+        Ok(SpinMutexGuard {
+            mutex: self,
+            _no_send: PhantomData,
+        })
+    }
+
+    /// Get a mutable reference to the protected value. No locking is needed
+    /// because `self` is borrowed mutably.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+}
+
+/// An RAII guard returned by [`SpinMutex::lock`] and
+/// [`SpinMutex::try_lock`]. Releases the spinlock when dropped. The
+/// destructor will panic if the release fails.
+///
+/// This type is `!Send` because a spinlock must be released by the
+/// processor that acquired it.
+pub struct SpinMutexGuard<'a, T: ?Sized> {
+    mutex: &'a SpinMutex<'a, T>,
+    _no_send: PhantomData<*mut ()>,
+}
+
+unsafe impl<T: ?Sized + Sync> Sync for SpinMutexGuard<'_, T> {}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for SpinMutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized> Deref for SpinMutexGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // Safety: We hold the spinlock
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for SpinMutexGuard<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: We hold the spinlock
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for SpinMutexGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.mutex.spinlock.unlock().unwrap();
+    }
+}
+
+#[cfg(any(all(feature = "solid_fmp3", feature = "dcre"), feature = "none"))]
+pub use self::owned::*;
+
+#[cfg(any(all(feature = "solid_fmp3", feature = "dcre"), feature = "none"))]
+#[cfg_attr(
+    feature = "doc_cfg",
+    doc(cfg(all(feature = "solid_fmp3", feature = "dcre")))
+)]
+mod owned {
+    use super::*;
+
+    /// The builder type for [spinlocks](Spinlock). Created by
+    /// [`Spinlock::build`].
+    #[cfg_attr(
+        feature = "doc_cfg",
+        doc(cfg(all(feature = "solid_fmp3", feature = "dcre")))
+    )]
+    #[must_use = "`Builder` creates nothing unless you call `.finish()`"]
+    pub struct Builder {
+        #[cfg(not(feature = "none"))]
+        raw: abi::T_CSPN,
+    }
+
+    impl Spinlock {
+        /// `acre_spn`: Create a builder for `Spinlock`.
+        ///
+        /// # Example
+        ///
+        /// ```rust,no_run
+        /// use itron::spinlock::Spinlock;
+        /// let spinlock = Spinlock::build()
+        ///     .finish()
+        ///     .expect("failed to create a spinlock");
+        ///
+        /// spinlock.as_ref().lock()
+        ///    .expect("failed to acquire the spinlock");
+        /// spinlock.as_ref().unlock()
+        ///    .expect("failed to release the spinlock");
+        /// ```
+        #[inline]
+        #[doc(alias = "acre_spn")]
+        pub fn build() -> Builder {
+            Builder {
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CSPN {
+                    spnatr: abi::TA_NULL,
+                },
+            }
+        }
+    }
+
+    impl Builder {
+        /// Create a spinlock using the specified parameters.
+        pub fn finish(self) -> Result<Spinlock, Error<BuildError>> {
+            match () {
+                #[cfg(not(feature = "none"))]
+                () => unsafe {
+                    let id = Error::err_if_negative(abi::acre_spn(&self.raw))?;
+                    // Safety: We own the spinlock we create
+                    Ok(Spinlock::from_raw_nonnull(abi::NonNullID::new_unchecked(
+                        id,
+                    )))
+                },
+                #[cfg(feature = "none")]
+                () => unimplemented!(),
+            }
+        }
+    }
+
+    /// An owned spinlock.
+    ///
+    /// [Deletes] the spinlock automatically when dropped. The destructor will
+    /// panic if the deletion fails.
+    ///
+    /// [Deletes]: SpinlockRef::delete
+    #[derive(PartialEq, Eq)]
+    #[cfg_attr(
+        feature = "doc_cfg",
+        doc(cfg(all(feature = "solid_fmp3", feature = "dcre")))
+    )]
+    pub struct Spinlock(SpinlockRef<'static>);
+
+    impl fmt::Debug for Spinlock {
+        #[inline]
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            self.0.fmt(f)
+        }
+    }
+
+    impl Drop for Spinlock {
+        #[inline]
+        fn drop(&mut self) {
+            unsafe { self.0.delete().unwrap() };
+        }
+    }
+
+    impl Spinlock {
+        /// Construct a `Spinlock` from a raw object ID.
+        ///
+        /// # Safety
+        ///
+        /// See [Object ID Wrappers](crate#object-id-wrappers).
+        #[inline]
+        pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
+            Self(unsafe { SpinlockRef::from_raw_nonnull(id) })
+        }
+
+        /// Consume and "leak" `self`, returning a reference `SpinlockRef<'a>`.
+        #[inline]
+        pub const fn leak<'a>(self) -> SpinlockRef<'a> {
+            let out = self.0;
+            core::mem::forget(self);
+            out
+        }
+
+        /// Get the raw object ID.
+        #[inline]
+        pub const fn as_raw(&self) -> abi::ID {
+            self.0.as_raw()
+        }
+
+        /// Get the raw object ID as [` abi::NonNullID`].
+        #[inline]
+        pub const fn as_raw_nonnull(&self) -> abi::NonNullID {
+            self.0.as_raw_nonnull()
+        }
+
+        /// Borrow `Spinlock` as [`SpinlockRef`].
+        ///
+        /// Use this to perform operations on spinlocks because most of the
+        /// methods are implemented on `SpinlockRef` but not `Spinlock`.
+        #[inline]
+        pub const fn as_ref(&self) -> SpinlockRef<'_> {
+            self.0
+        }
+    }
+}
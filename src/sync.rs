@@ -0,0 +1,258 @@
+//! Synchronization primitives built on top of kernel objects
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use crate::{
+    error::Error,
+    mutex::{LockError, LockTimeoutError, MutexRef, TryLockError},
+    time::Timeout,
+};
+
+#[cfg(feature = "dcre")]
+use core::mem::ManuallyDrop;
+
+mod pool;
+pub use self::pool::*;
+
+#[cfg(all(feature = "lock_api", feature = "dcre"))]
+mod raw;
+#[cfg(all(feature = "lock_api", feature = "dcre"))]
+pub use self::raw::*;
+
+/// A mutual exclusion primitive protecting data shared between tasks,
+/// backed by a [mutex](crate::mutex).
+///
+/// The mutex is locked while a [`MutexGuard`] is alive. The TOPPERS
+/// third-generation kernels require mutexes to be unlocked in the reverse
+/// order of locking. Dropping guards in a different order causes the
+/// destructor of a guard to panic.
+///
+/// # Errors
+///
+/// The lock methods report the kernel's errors as they are. In particular:
+///
+///  - Locking a mutex that is already owned by the calling task fails with
+///    `Deadlock`.
+///
+///  - Locking a priority-ceiling mutex from a task whose base priority is
+///    higher than the ceiling fails with `BadParam`.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use itron::{abi, mutex::MutexRef, sync::Mutex};
+///
+/// static COUNTER: Mutex<'static, u32> = unsafe {
+///     Mutex::new(
+///         MutexRef::from_raw_nonnull(abi::NonNullID::new_unchecked(1)),
+///         0,
+///     )
+/// };
+///
+/// *COUNTER.lock().expect("failed to lock the mutex") += 1;
+/// ```
+pub struct Mutex<'a, T: ?Sized> {
+    mutex: MutexRef<'a>,
+    /// Indicates whether `mutex` should be deleted when `self` is dropped.
+    #[cfg(feature = "dcre")]
+    owned: bool,
+    value: UnsafeCell<T>,
+}
+
+unsafe impl<T: ?Sized + Send> Send for Mutex<'_, T> {}
+unsafe impl<T: ?Sized + Send> Sync for Mutex<'_, T> {}
+
+impl<T: ?Sized> fmt::Debug for Mutex<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Mutex")
+            .field("mutex", &self.mutex)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T> Mutex<'a, T> {
+    /// Construct a `Mutex` protecting `value` with `mutex`.
+    ///
+    /// # Safety
+    ///
+    /// For as long as the returned `Mutex` is alive, `mutex` must not be
+    /// unlocked or initialized by anything but the returned `Mutex`'s guards
+    /// (e.g., by calling [`MutexRef::unlock`] or [`MutexRef::initialize`]
+    /// directly). Otherwise, another task could lock `mutex` while a guard
+    /// is alive, obtaining a second mutable reference to the protected value.
+    #[inline]
+    pub const unsafe fn new(mutex: MutexRef<'a>, value: T) -> Self {
+        Self {
+            mutex,
+            #[cfg(feature = "dcre")]
+            owned: false,
+            value: UnsafeCell::new(value),
+        }
+    }
+}
+
+#[cfg(feature = "dcre")]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+impl<T> Mutex<'static, T> {
+    /// Construct a `Mutex` protecting `value` with the owned mutex `mutex`.
+    /// `mutex` will be deleted when the returned `Mutex` is dropped.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use itron::{mutex, sync::Mutex};
+    /// let counter = Mutex::from_mutex(
+    ///     mutex::Mutex::build()
+    ///         .finish()
+    ///         .expect("failed to create a mutex"),
+    ///     0u32,
+    /// );
+    ///
+    /// *counter.lock().expect("failed to lock the mutex") += 1;
+    /// ```
+    #[inline]
+    pub fn from_mutex(mutex: crate::mutex::Mutex, value: T) -> Self {
+        Self {
+            mutex: mutex.leak(),
+            owned: true,
+            value: UnsafeCell::new(value),
+        }
+    }
+}
+
+impl<T> Mutex<'_, T> {
+    /// Consume `self`, returning the protected value.
+    #[inline]
+    pub fn into_inner(self) -> T {
+        match () {
+            #[cfg(feature = "dcre")]
+            () => {
+                let this = ManuallyDrop::new(self);
+                this.delete_if_owned();
+                // Safety: `this.value` is never used again
+                unsafe { core::ptr::read(&this.value) }.into_inner()
+            }
+            #[cfg(not(feature = "dcre"))]
+            () => self.value.into_inner(),
+        }
+    }
+}
+
+impl<'a, T: ?Sized> Mutex<'a, T> {
+    /// Get the underlying mutex.
+    ///
+    /// # Safety
+    ///
+    /// The returned [`MutexRef`] must not be used to unlock or initialize
+    /// the mutex (e.g., by calling [`MutexRef::unlock`] or
+    /// [`MutexRef::initialize`]) while `self` is alive. See [`Self::new`].
+    #[inline]
+    pub const unsafe fn mutex(&self) -> MutexRef<'a> {
+        self.mutex
+    }
+
+    /// Lock the mutex, waiting until it becomes available, and return a
+    /// guard granting access to the protected value.
+    #[inline]
+    pub fn lock(&self) -> Result<MutexGuard<'_, T>, Error<LockError>> {
+        self.mutex.lock()?;
+        Ok(MutexGuard {
+            mutex: self,
+            _no_send: PhantomData,
+        })
+    }
+
+    /// [`lock`](Self::lock) with timeout.
+    #[inline]
+    pub fn lock_timeout(&self, tmo: Timeout) -> Result<MutexGuard<'_, T>, Error<LockTimeoutError>> {
+        self.mutex.lock_timeout(tmo)?;
+        Ok(MutexGuard {
+            mutex: self,
+            _no_send: PhantomData,
+        })
+    }
+
+    /// Attempt to lock the mutex and return a guard granting access to the
+    /// protected value. Fails and returns immediately if the mutex is owned
+    /// by another task.
+    #[inline]
+    pub fn try_lock(&self) -> Result<MutexGuard<'_, T>, Error<TryLockError>> {
+        self.mutex.try_lock()?;
+        Ok(MutexGuard {
+            mutex: self,
+            _no_send: PhantomData,
+        })
+    }
+
+    /// Get a mutable reference to the protected value. No locking is needed
+    /// because `self` is borrowed mutably.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut T {
+        self.value.get_mut()
+    }
+
+    #[cfg(feature = "dcre")]
+    #[inline]
+    fn delete_if_owned(&self) {
+        if self.owned {
+            // Safety: We own the mutex
+            unsafe { self.mutex.delete().unwrap() };
+        }
+    }
+}
+
+#[cfg(feature = "dcre")]
+impl<T: ?Sized> Drop for Mutex<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.delete_if_owned();
+    }
+}
+
+/// An RAII guard returned by [`Mutex::lock`], [`Mutex::lock_timeout`], and
+/// [`Mutex::try_lock`]. Unlocks the mutex when dropped. The destructor will
+/// panic if the unlocking fails.
+///
+/// This type is `!Send` because a mutex must be unlocked by the task that
+/// locked it.
+pub struct MutexGuard<'a, T: ?Sized> {
+    mutex: &'a Mutex<'a, T>,
+    _no_send: PhantomData<*mut ()>,
+}
+
+unsafe impl<T: ?Sized + Sync> Sync for MutexGuard<'_, T> {}
+
+impl<T: ?Sized + fmt::Debug> fmt::Debug for MutexGuard<'_, T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T: ?Sized> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // Safety: We own the mutex
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized> DerefMut for MutexGuard<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: We own the mutex
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
+
+impl<T: ?Sized> Drop for MutexGuard<'_, T> {
+    #[inline]
+    fn drop(&mut self) {
+        self.mutex.mutex.unlock().unwrap();
+    }
+}
@@ -0,0 +1,180 @@
+use core::{
+    cell::UnsafeCell,
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::atomic::{AtomicBool, Ordering},
+};
+
+use crate::{
+    error::Error,
+    semaphore::{Permit, PollError, SemaphoreRef, WaitError, WaitTimeoutError},
+    time::Timeout,
+};
+
+/// A pool of `N` resources of type `T`, each of which can be used by one
+/// task at a time. A counting semaphore tracks the number of available
+/// resources, so tasks block until a resource becomes available.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use itron::{abi, semaphore::SemaphoreRef, sync::ResourcePool};
+///
+/// // Allow up to two tasks to write to the flash memory at the same time.
+/// // The semaphore's initial count is two.
+/// static WRITE_BUFFERS: ResourcePool<'static, [u8; 256], 2> = unsafe {
+///     ResourcePool::new(
+///         SemaphoreRef::from_raw_nonnull(abi::NonNullID::new_unchecked(1)),
+///         [[0; 256]; 2],
+///     )
+/// };
+///
+/// let mut buffer = WRITE_BUFFERS.acquire().expect("failed to acquire a buffer");
+/// buffer[0] = 42;
+/// ```
+pub struct ResourcePool<'a, T, const N: usize> {
+    semaphore: SemaphoreRef<'a>,
+    in_use: [AtomicBool; N],
+    resources: UnsafeCell<[T; N]>,
+}
+
+unsafe impl<T: Send, const N: usize> Send for ResourcePool<'_, T, N> {}
+unsafe impl<T: Send, const N: usize> Sync for ResourcePool<'_, T, N> {}
+
+impl<T, const N: usize> fmt::Debug for ResourcePool<'_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourcePool")
+            .field("semaphore", &self.semaphore)
+            .field("in_use", &self.in_use)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<'a, T, const N: usize> ResourcePool<'a, T, N> {
+    /// Construct a `ResourcePool` managing `resources` with `semaphore`.
+    ///
+    /// # Safety
+    ///
+    /// `semaphore`'s count must be equal to `N` when the pool is first used,
+    /// and `semaphore` must not be operated by anything but the pool (e.g.,
+    /// by calling [`SemaphoreRef::signal`] directly) afterward.
+    #[inline]
+    pub const unsafe fn new(semaphore: SemaphoreRef<'a>, resources: [T; N]) -> Self {
+        #[allow(clippy::declare_interior_mutable_const)]
+        const NOT_IN_USE: AtomicBool = AtomicBool::new(false);
+        Self {
+            semaphore,
+            in_use: [NOT_IN_USE; N],
+            resources: UnsafeCell::new(resources),
+        }
+    }
+
+    /// Consume `self`, returning the resources.
+    #[inline]
+    pub fn into_inner(self) -> [T; N] {
+        self.resources.into_inner()
+    }
+
+    /// Get the underlying semaphore.
+    #[inline]
+    pub const fn semaphore(&self) -> SemaphoreRef<'a> {
+        self.semaphore
+    }
+
+    /// Wait until a resource becomes available and take it.
+    #[inline]
+    pub fn acquire(&self) -> Result<ResourceGuard<'_, 'a, T, N>, Error<WaitError>> {
+        Ok(self.take(self.semaphore.acquire()?))
+    }
+
+    /// [`acquire`](Self::acquire) with timeout.
+    #[inline]
+    pub fn acquire_timeout(
+        &self,
+        tmo: Timeout,
+    ) -> Result<ResourceGuard<'_, 'a, T, N>, Error<WaitTimeoutError>> {
+        Ok(self.take(self.semaphore.acquire_timeout(tmo)?))
+    }
+
+    /// Take an available resource. Fails and returns immediately if there's
+    /// none.
+    #[inline]
+    pub fn try_acquire(&self) -> Result<ResourceGuard<'_, 'a, T, N>, Error<PollError>> {
+        Ok(self.take(self.semaphore.try_acquire()?))
+    }
+
+    /// Get mutable references to the resources. No locking is needed because
+    /// `self` is borrowed mutably.
+    #[inline]
+    pub fn get_mut(&mut self) -> &mut [T; N] {
+        self.resources.get_mut()
+    }
+
+    /// Find a free slot. `permit` guarantees that there's at least one.
+    fn take(&self, permit: Permit<'a>) -> ResourceGuard<'_, 'a, T, N> {
+        let index = self
+            .in_use
+            .iter()
+            .position(|in_use| {
+                in_use
+                    .compare_exchange(false, true, Ordering::Acquire, Ordering::Relaxed)
+                    .is_ok()
+            })
+            .expect("semaphore count is inconsistent with the pool");
+        ResourceGuard {
+            pool: self,
+            index,
+            _permit: permit,
+        }
+    }
+}
+
+/// An RAII guard granting access to a resource taken from a
+/// [`ResourcePool`]. Returns the resource to the pool when dropped.
+pub struct ResourceGuard<'p, 'a, T, const N: usize> {
+    pool: &'p ResourcePool<'a, T, N>,
+    index: usize,
+    /// Signals the semaphore after `Drop::drop` releases the slot
+    _permit: Permit<'a>,
+}
+
+unsafe impl<T: Sync, const N: usize> Sync for ResourceGuard<'_, '_, T, N> {}
+
+impl<T: fmt::Debug, const N: usize> fmt::Debug for ResourceGuard<'_, '_, T, N> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        (**self).fmt(f)
+    }
+}
+
+impl<T, const N: usize> ResourceGuard<'_, '_, T, N> {
+    /// Get the index of the resource in the pool.
+    #[inline]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+impl<T, const N: usize> Deref for ResourceGuard<'_, '_, T, N> {
+    type Target = T;
+
+    #[inline]
+    fn deref(&self) -> &T {
+        // Safety: We own the slot
+        unsafe { &*(self.pool.resources.get() as *const T).add(self.index) }
+    }
+}
+
+impl<T, const N: usize> DerefMut for ResourceGuard<'_, '_, T, N> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut T {
+        // Safety: We own the slot
+        unsafe { &mut *(self.pool.resources.get() as *mut T).add(self.index) }
+    }
+}
+
+impl<T, const N: usize> Drop for ResourceGuard<'_, '_, T, N> {
+    #[inline]
+    fn drop(&mut self) {
+        self.pool.in_use[self.index].store(false, Ordering::Release);
+    }
+}
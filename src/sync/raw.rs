@@ -0,0 +1,308 @@
+use core::{
+    convert::TryFrom,
+    mem,
+    sync::atomic::{AtomicI32, Ordering},
+};
+
+use crate::{
+    abi,
+    mutex::{self, MutexRef},
+    semaphore::{self, SemaphoreRef},
+    time::{Duration, Instant, Timeout},
+};
+
+/// A [`lock_api::RawMutex`] implementation backed by a [mutex](crate::mutex).
+///
+/// The mutex is created when it's locked for the first time and deleted
+/// when `RawMutex` is dropped. The kernel's mutex ownership rules apply: the
+/// mutex must be unlocked by the task that locked it, and mutexes must be
+/// unlocked in the reverse order of locking.
+///
+/// The methods panic if the mutex can't be created or the kernel reports an
+/// error (e.g., because they were called from a non-task context or the
+/// calling task already owns the mutex).
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use itron::sync::RawMutex;
+/// static COUNTER: lock_api::Mutex<RawMutex, u32> = lock_api::Mutex::const_new(
+///     <RawMutex as lock_api::RawMutex>::INIT,
+///     0,
+/// );
+///
+/// *COUNTER.lock() += 1;
+/// ```
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "doc_cfg",
+    doc(cfg(all(feature = "lock_api", feature = "dcre")))
+)]
+pub struct RawMutex {
+    /// The mutex ID, or `0` if the mutex hasn't been created yet.
+    id: AtomicI32,
+}
+
+impl RawMutex {
+    /// Get the mutex, creating it if it doesn't exist yet.
+    fn mutex(&self) -> MutexRef<'_> {
+        let id = get_or_create(
+            &self.id,
+            || {
+                mutex::Mutex::build()
+                    .finish()
+                    .expect("failed to create a mutex")
+            },
+            mutex::Mutex::as_raw_nonnull,
+        );
+        // Safety: We own the mutex
+        unsafe { MutexRef::from_raw_nonnull(id) }
+    }
+}
+
+unsafe impl lock_api::RawMutex for RawMutex {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        id: AtomicI32::new(0),
+    };
+
+    /// A mutex must be unlocked by the task that locked it.
+    type GuardMarker = lock_api::GuardNoSend;
+
+    #[inline]
+    fn lock(&self) {
+        self.mutex().lock().expect("failed to lock the mutex");
+    }
+
+    #[inline]
+    fn try_lock(&self) -> bool {
+        match self.mutex().try_lock() {
+            Ok(()) => true,
+            #[cfg(not(feature = "none"))]
+            Err(e) if matches!(e.kind(), mutex::TryLockError::Timeout(_)) => false,
+            Err(e) => panic!("failed to lock the mutex: {:?}", e),
+        }
+    }
+
+    #[inline]
+    unsafe fn unlock(&self) {
+        self.mutex().unlock().expect("failed to unlock the mutex");
+    }
+
+    #[inline]
+    fn is_locked(&self) -> bool {
+        self.mutex()
+            .info()
+            .expect("failed to get the mutex's state")
+            .owning_task_id()
+            .is_some()
+    }
+}
+
+unsafe impl lock_api::RawMutexTimed for RawMutex {
+    type Duration = Duration;
+    type Instant = Instant;
+
+    #[inline]
+    fn try_lock_for(&self, timeout: Self::Duration) -> bool {
+        self.try_lock_timeout(timeout_for(timeout))
+    }
+
+    #[inline]
+    fn try_lock_until(&self, timeout: Self::Instant) -> bool {
+        lock_until(timeout, |tmo| self.try_lock_timeout(tmo))
+    }
+}
+
+impl RawMutex {
+    fn try_lock_timeout(&self, tmo: Timeout) -> bool {
+        match self.mutex().lock_timeout(tmo) {
+            Ok(()) => true,
+            #[cfg(not(feature = "none"))]
+            Err(e) if matches!(e.kind(), mutex::LockTimeoutError::Timeout(_)) => false,
+            Err(e) => panic!("failed to lock the mutex: {:?}", e),
+        }
+    }
+}
+
+impl Drop for RawMutex {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(id) = abi::NonNullID::new(*self.id.get_mut()) {
+            // Safety: We own the mutex
+            unsafe { MutexRef::from_raw_nonnull(id).delete().unwrap() };
+        }
+    }
+}
+
+/// A [`lock_api::RawMutex`] implementation backed by a binary
+/// [semaphore](crate::semaphore).
+///
+/// Unlike [`RawMutex`], this type isn't subject to the kernel's mutex
+/// ownership rules. A lock can be released by a task other than the one
+/// that acquired it, and locks can be released in any order. On the other
+/// hand, it doesn't provide priority inheritance or priority ceiling.
+///
+/// The semaphore is created when it's locked for the first time and deleted
+/// when `RawSemaphoreMutex` is dropped. The methods panic if the semaphore
+/// can't be created or the kernel reports an error (e.g., because they were
+/// called from a non-task context).
+#[derive(Debug)]
+#[cfg_attr(
+    feature = "doc_cfg",
+    doc(cfg(all(feature = "lock_api", feature = "dcre")))
+)]
+pub struct RawSemaphoreMutex {
+    /// The semaphore ID, or `0` if the semaphore hasn't been created yet.
+    id: AtomicI32,
+}
+
+impl RawSemaphoreMutex {
+    /// Get the semaphore, creating it if it doesn't exist yet.
+    fn semaphore(&self) -> SemaphoreRef<'_> {
+        let id = get_or_create(
+            &self.id,
+            || {
+                semaphore::Semaphore::build()
+                    .initial_count(1)
+                    .max_count(1)
+                    .finish()
+                    .expect("failed to create a semaphore")
+            },
+            semaphore::Semaphore::as_raw_nonnull,
+        );
+        // Safety: We own the semaphore
+        unsafe { SemaphoreRef::from_raw_nonnull(id) }
+    }
+
+    fn try_lock_timeout(&self, tmo: Timeout) -> bool {
+        match self.semaphore().wait_timeout(tmo) {
+            Ok(()) => true,
+            #[cfg(not(feature = "none"))]
+            Err(e) if matches!(e.kind(), semaphore::WaitTimeoutError::Timeout(_)) => false,
+            Err(e) => panic!("failed to lock the semaphore: {:?}", e),
+        }
+    }
+}
+
+unsafe impl lock_api::RawMutex for RawSemaphoreMutex {
+    #[allow(clippy::declare_interior_mutable_const)]
+    const INIT: Self = Self {
+        id: AtomicI32::new(0),
+    };
+
+    type GuardMarker = lock_api::GuardSend;
+
+    #[inline]
+    fn lock(&self) {
+        self.semaphore()
+            .wait()
+            .expect("failed to lock the semaphore");
+    }
+
+    #[inline]
+    fn try_lock(&self) -> bool {
+        match self.semaphore().poll() {
+            Ok(()) => true,
+            #[cfg(not(feature = "none"))]
+            Err(e) if matches!(e.kind(), semaphore::PollError::Timeout(_)) => false,
+            Err(e) => panic!("failed to lock the semaphore: {:?}", e),
+        }
+    }
+
+    #[inline]
+    unsafe fn unlock(&self) {
+        self.semaphore()
+            .signal()
+            .expect("failed to unlock the semaphore");
+    }
+
+    #[inline]
+    fn is_locked(&self) -> bool {
+        self.semaphore()
+            .info()
+            .expect("failed to get the semaphore's state")
+            .count()
+            == 0
+    }
+}
+
+unsafe impl lock_api::RawMutexTimed for RawSemaphoreMutex {
+    type Duration = Duration;
+    type Instant = Instant;
+
+    #[inline]
+    fn try_lock_for(&self, timeout: Self::Duration) -> bool {
+        self.try_lock_timeout(timeout_for(timeout))
+    }
+
+    #[inline]
+    fn try_lock_until(&self, timeout: Self::Instant) -> bool {
+        lock_until(timeout, |tmo| self.try_lock_timeout(tmo))
+    }
+}
+
+impl Drop for RawSemaphoreMutex {
+    #[inline]
+    fn drop(&mut self) {
+        if let Some(id) = abi::NonNullID::new(*self.id.get_mut()) {
+            // Safety: We own the semaphore
+            unsafe { SemaphoreRef::from_raw_nonnull(id).delete().unwrap() };
+        }
+    }
+}
+
+/// Get the object ID stored in `cell`, creating an owned object by `create`
+/// and transferring its ownership to `cell` if there's none yet. If another
+/// task fills `cell` first, the created object is deleted.
+fn get_or_create<T>(
+    cell: &AtomicI32,
+    create: impl FnOnce() -> T,
+    id_of: impl FnOnce(&T) -> abi::NonNullID,
+) -> abi::NonNullID {
+    if let Some(id) = abi::NonNullID::new(cell.load(Ordering::Acquire)) {
+        return id;
+    }
+
+    let object = create();
+    let new_id = id_of(&object);
+    match cell.compare_exchange(0, new_id.get(), Ordering::AcqRel, Ordering::Acquire) {
+        Ok(_) => {
+            // `cell` owns the object now
+            mem::forget(object);
+            new_id
+        }
+        Err(id) => {
+            // Delete the object we created
+            drop(object);
+            abi::NonNullID::new(id).unwrap()
+        }
+    }
+}
+
+/// Call `try_lock_timeout` with the timeout until `deadline`. A deadline in
+/// the past results in polling. If `deadline` is more than `TMAX_RELTIM` away,
+/// wait for `TMAX_RELTIM` at a time and re-check the deadline between the
+/// waits.
+fn lock_until(deadline: Instant, mut try_lock_timeout: impl FnMut(Timeout) -> bool) -> bool {
+    loop {
+        let remaining = deadline.as_raw().saturating_sub(Instant::now().as_raw());
+        if let Some(tmo) = Timeout::from_micros(remaining) {
+            return try_lock_timeout(tmo);
+        }
+
+        // Safety: It's a valid timeout value
+        let max_tmo = unsafe { Timeout::from_raw(abi::TMAX_RELTIM) };
+        if try_lock_timeout(max_tmo) {
+            return true;
+        }
+    }
+}
+
+/// Convert `duration` to `Timeout`.
+fn timeout_for(duration: Duration) -> Timeout {
+    match Timeout::try_from(duration) {
+        Ok(tmo) => tmo,
+        Err(_) => panic!("duration is too long"),
+    }
+}
@@ -8,9 +8,9 @@ use crate::{
     time::{Duration, Timeout},
 };
 
-// TODO: sta_ovr
-// TODO: stp_ovr
-// TODO: ref_ovr
+#[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
+use crate::time::ProcessorTime;
+
 // TODO: chg_spr
 // TODO: chg_spr
 // TODO: TA_NOACTQUE
@@ -618,6 +618,123 @@ impl ErrorKind for RaiseTerminationError {
     }
 }
 
+define_error_kind! {
+    /// Error type for [`TaskRef::start_overrun_timer`].
+    #[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
+    #[cfg_attr(
+        feature = "doc_cfg",
+        doc(cfg(all(feature = "asp3", feature = "ovrhdr")))
+    )]
+    pub enum StartOverrunTimerError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// No overrun handler is registered.
+        #[cfg(not(feature = "none"))]
+        NotSupported,
+        /// The processor time is zero or larger than `TMAX_OVRTIM`.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+    }
+}
+
+#[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
+impl ErrorKind for StartOverrunTimerError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`TaskRef::stop_overrun_timer`].
+    #[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
+    #[cfg_attr(
+        feature = "doc_cfg",
+        doc(cfg(all(feature = "asp3", feature = "ovrhdr")))
+    )]
+    pub enum StopOverrunTimerError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// No overrun handler is registered.
+        #[cfg(not(feature = "none"))]
+        NotSupported,
+    }
+}
+
+#[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
+impl ErrorKind for StopOverrunTimerError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`TaskRef::overrun_info`].
+    #[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
+    #[cfg_attr(
+        feature = "doc_cfg",
+        doc(cfg(all(feature = "asp3", feature = "ovrhdr")))
+    )]
+    pub enum OverrunInfoError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(not(feature = "none"))]
+        BadId,
+        #[cfg(any())]
+        AccessDenied,
+        /// No overrun handler is registered.
+        #[cfg(not(feature = "none"))]
+        NotSupported,
+    }
+}
+
+#[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
+impl ErrorKind for OverrunInfoError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            // `E_MACV` is considered critical, hence excluded
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_ID | abi::E_NOEXS => Some(Self::BadId(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_NOSPT => Some(Self::NotSupported(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
 define_error_kind! {
     /// Error type for [`sleep`].
     pub enum SleepError {
@@ -826,6 +943,9 @@ define_error_kind! {
         ///  - The caller requested to create a system task, `sstksz != 0`,
         ///    and `stk` is non-null (NGKI1071, `E_PAR`).
         ///
+        ///  - The [`TaskStack`] specified by [`Builder::stack`] is already in
+        ///    use by another task (`E_PAR`).
+        ///
         #[cfg(not(feature = "none"))]
         BadParam,
     }
@@ -877,6 +997,73 @@ impl State {
     }
 }
 
+/// The reason a task is waiting, returned by [`Info::wait_cause`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum WaitCause {
+    /// `TTW_SLP`: Waiting for a wakeup request ([`sleep`]).
+    Sleep,
+    /// `TTW_DLY`: Waiting for a time to elapse ([`delay`]).
+    Delay,
+    /// `TTW_SEM`: Waiting to acquire a semaphore.
+    Semaphore,
+    /// `TTW_FLG`: Waiting for an eventflag.
+    Eventflag,
+    /// `TTW_SDTQ`: Waiting to send to a dataqueue.
+    SendDataqueue,
+    /// `TTW_RDTQ`: Waiting to receive from a dataqueue.
+    ReceiveDataqueue,
+    /// `TTW_SPDQ`: Waiting to send to a priority dataqueue.
+    SendPriorityDataqueue,
+    /// `TTW_RPDQ`: Waiting to receive from a priority dataqueue.
+    ReceivePriorityDataqueue,
+    /// `TTW_SMBF`: Waiting to send to a message buffer.
+    SendMessageBuffer,
+    /// `TTW_RMBF`: Waiting to receive from a message buffer.
+    ReceiveMessageBuffer,
+    /// `TTW_MTX`: Waiting to lock a mutex.
+    Mutex,
+    /// `TTW_MPF`: Waiting to acquire a memory block from a fixed-sized
+    /// memory pool.
+    MemoryPool,
+}
+
+impl WaitCause {
+    #[cfg(not(feature = "none"))]
+    #[inline]
+    fn from_abi(x: abi::STAT) -> Option<Self> {
+        match x {
+            abi::TTW_SLP => Some(Self::Sleep),
+            abi::TTW_DLY => Some(Self::Delay),
+            abi::TTW_SEM => Some(Self::Semaphore),
+            abi::TTW_FLG => Some(Self::Eventflag),
+            abi::TTW_SDTQ => Some(Self::SendDataqueue),
+            abi::TTW_RDTQ => Some(Self::ReceiveDataqueue),
+            abi::TTW_SPDQ => Some(Self::SendPriorityDataqueue),
+            abi::TTW_RPDQ => Some(Self::ReceivePriorityDataqueue),
+            #[cfg(any(
+                all(feature = "asp3", feature = "messagebuf"),
+                all(feature = "solid_asp3", feature = "messagebuf")
+            ))]
+            abi::TTW_SMBF => Some(Self::SendMessageBuffer),
+            #[cfg(any(
+                all(feature = "asp3", feature = "messagebuf"),
+                all(feature = "solid_asp3", feature = "messagebuf")
+            ))]
+            abi::TTW_RMBF => Some(Self::ReceiveMessageBuffer),
+            abi::TTW_MTX => Some(Self::Mutex),
+            abi::TTW_MPF => Some(Self::MemoryPool),
+            // Unknown to us
+            _ => None,
+        }
+    }
+
+    /// Get a flag indicating whether the task is waiting on a kernel object.
+    #[inline]
+    pub fn is_object(self) -> bool {
+        !matches!(self, Self::Sleep | Self::Delay)
+    }
+}
+
 /// Task information returned by [`TaskRef::info`].
 #[derive(Debug, Clone, Copy)]
 pub struct Info {
@@ -918,15 +1105,143 @@ impl Info {
         }
     }
 
-    // TODO: tskwait
-    // TODO: wobjid
-    // TODO: lefttmo
-    // TODO: actcnt
-    // TODO: wupcnt
-    // TODO: raster
-    // TODO: dister
-    // TODO: prcid
-    // TODO: actprc
+    /// Get the reason the task is waiting. Returns `None` if the task is not
+    /// in the [`Waiting`] or [`WaitingSuspended`] state.
+    ///
+    /// [`Waiting`]: State::Waiting
+    /// [`WaitingSuspended`]: State::WaitingSuspended
+    #[inline]
+    pub fn wait_cause(&self) -> Option<WaitCause> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => {
+                if (self.raw.tskstat & abi::TTS_WAI) == 0 {
+                    return None;
+                }
+                WaitCause::from_abi(self.raw.tskwait)
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the ID of the object the task is waiting on. Returns `None` if the
+    /// task is not waiting on an object.
+    #[inline]
+    pub fn wait_object_id(&self) -> Option<abi::NonNullID> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => {
+                if self.wait_cause()?.is_object() {
+                    abi::NonNullID::new(self.raw.wobjid)
+                } else {
+                    None
+                }
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the remaining time until the task's wait times out. Returns `None`
+    /// if the task is not waiting. Returns [`Timeout::FOREVER`] if the wait
+    /// has no timeout.
+    #[inline]
+    pub fn timeout_left(&self) -> Option<Timeout> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => {
+                self.wait_cause()?;
+                // Safety: The kernel reports a valid timeout value
+                Some(unsafe { Timeout::from_raw(self.raw.lefttmo) })
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the number of queued activation requests.
+    #[inline]
+    pub fn activation_count(&self) -> usize {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => self.raw.actcnt as usize,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the number of queued wakeup requests. The returned value is
+    /// meaningless if the task is in the [`Dormant`] state.
+    ///
+    /// [`Dormant`]: State::Dormant
+    #[inline]
+    pub fn wakeup_count(&self) -> usize {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => self.raw.wupcnt as usize,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get a flag indicating whether a termination request is pending.
+    #[inline]
+    pub fn is_termination_requested(&self) -> bool {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => self.raw.raster != 0,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get a flag indicating whether termination is disabled.
+    #[inline]
+    pub fn is_termination_disabled(&self) -> bool {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => self.raw.dister != 0,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the processor to which the task is assigned.
+    #[inline]
+    pub fn processor(&self) -> Processor {
+        match () {
+            #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+            () => Processor::from_raw(self.raw.prcid).unwrap(),
+            #[cfg(not(any(feature = "none", feature = "fmp3", feature = "solid_fmp3")))]
+            () => Processor::UNIPROCESSOR,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the processor to which the task will be assigned when it's
+    /// activated by the next queued activation request. Returns `None` if no
+    /// activation requests are queued.
+    #[inline]
+    pub fn next_activation_processor(&self) -> Option<Processor> {
+        match () {
+            // `actprc` is `TPRC_NONE` (`0`) if no activation requests are
+            // queued
+            #[cfg(any(feature = "fmp3", feature = "solid_fmp3"))]
+            () => Processor::from_raw(self.raw.actprc),
+            #[cfg(not(any(feature = "none", feature = "fmp3", feature = "solid_fmp3")))]
+            () => {
+                if self.activation_count() > 0 {
+                    Some(Processor::UNIPROCESSOR)
+                } else {
+                    None
+                }
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
 }
 
 /// `slp_tsk`: Put the current task to sleep.
@@ -1068,6 +1383,50 @@ pub fn current_id() -> Result<Option<abi::NonNullID>, Error<CurrentIdError>> {
     }
 }
 
+/// Overrun handler information returned by [`TaskRef::overrun_info`].
+#[derive(Debug, Clone, Copy)]
+#[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
+#[cfg_attr(
+    feature = "doc_cfg",
+    doc(cfg(all(feature = "asp3", feature = "ovrhdr")))
+)]
+pub struct OverrunInfo {
+    #[cfg(not(feature = "none"))]
+    raw: abi::T_ROVR,
+}
+
+#[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
+impl OverrunInfo {
+    /// Get a flag indicating whether the overrun timer is running.
+    #[inline]
+    pub fn is_active(&self) -> bool {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => self.raw.ovrstat == abi::TOVR_STA,
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the remaining processor time until the overrun handler is
+    /// invoked. Returns `None` if the overrun timer is not running.
+    #[inline]
+    pub fn time_left(&self) -> Option<ProcessorTime> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => {
+                if self.is_active() {
+                    Some(ProcessorTime::from_raw(self.raw.leftotm))
+                } else {
+                    None
+                }
+            }
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
 /// A borrowed reference to a task.
 #[derive(PartialEq, Eq, Clone, Copy)]
 pub struct TaskRef<'a> {
@@ -1410,6 +1769,67 @@ impl TaskRef<'_> {
     }
 }
 
+/// # Overrun Handler
+#[cfg(any(all(feature = "asp3", feature = "ovrhdr"), feature = "none"))]
+#[cfg_attr(
+    feature = "doc_cfg",
+    doc(cfg(all(feature = "asp3", feature = "ovrhdr")))
+)]
+impl TaskRef<'_> {
+    /// `sta_ovr`: Start the task's overrun timer. The overrun handler will
+    /// be invoked when the task has been running for the specified amount of
+    /// processor time. Restarts the timer if it's already running.
+    #[inline]
+    #[doc(alias = "sta_ovr")]
+    pub fn start_overrun_timer(
+        self,
+        budget: ProcessorTime,
+    ) -> Result<(), Error<StartOverrunTimerError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::sta_ovr(self.as_raw(), budget.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `stp_ovr`: Stop the task's overrun timer.
+    #[inline]
+    #[doc(alias = "stp_ovr")]
+    pub fn stop_overrun_timer(self) -> Result<(), Error<StopOverrunTimerError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::stp_ovr(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `ref_ovr`: Get the state of the task's overrun timer.
+    #[inline]
+    #[doc(alias = "ref_ovr")]
+    pub fn overrun_info(self) -> Result<OverrunInfo, Error<OverrunInfoError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut pri = MaybeUninit::uninit();
+                Error::err_if_negative(abi::ref_ovr(self.as_raw(), pri.as_mut_ptr()))?;
+                Ok(OverrunInfo {
+                    raw: pri.assume_init(),
+                })
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+}
+
 /// Get a reference to the current task.
 ///
 /// This function fails if it's called from an interrupt context or the CPU
@@ -1473,6 +1893,10 @@ pub use self::owned::*;
 #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
 mod owned {
     use super::*;
+    use core::{
+        cell::UnsafeCell,
+        sync::atomic::{AtomicU8, Ordering},
+    };
 
     /// An instance of [`IntoProcessorSet`] specifying all processors.
     #[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
@@ -1576,6 +2000,182 @@ mod owned {
         impl Sealed for AllProcessors {}
     }
 
+    /// A statically allocated stack region for a task created by
+    /// [`Builder::stack`].
+    ///
+    /// A `TaskStack` can be used by only one task at a time. It's claimed when
+    /// the task is created and released when the owning [`Task`] is dropped.
+    /// It remains in use if the `Task` is [leaked](Task::leak). The region is
+    /// aligned to 16 bytes, and its size (`N`) should be a multiple of the
+    /// kernel's stack alignment requirement.
+    ///
+    /// # Example
+    ///
+    /// ```rust,no_run
+    /// use itron::task::{Task, TaskStack};
+    /// static STACK: TaskStack<4096> = TaskStack::with_fill_pattern(0xa5);
+    /// let task = Task::build()
+    ///     .start(|| {})
+    ///     .stack(&STACK)
+    ///     .initial_priority(4)
+    ///     .finish_and_activate()
+    ///     .expect("failed to create a task")
+    ///     .leak();
+    ///
+    /// // Later, after the task has exited...
+    /// // Safety: The task is dormant, so nothing is running on `STACK`
+    /// let peak_usage = unsafe { STACK.high_water_mark() }.unwrap();
+    /// ```
+    #[repr(C, align(16))]
+    #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+    pub struct TaskStack<const N: usize> {
+        region: UnsafeCell<[MaybeUninit<u8>; N]>,
+        fill_pattern: Option<u8>,
+        state: AtomicU8,
+    }
+
+    /// `TaskStack::state` values
+    const STACK_UNUSED: u8 = 0;
+    const STACK_CLAIMED: u8 = 1;
+    const STACK_READY: u8 = 2;
+
+    // Safety: `region` is only accessed by the task that owns it, except
+    //         for the reads in `high_water_mark`, whose caller guarantees
+    //         that no task is running on the region
+    unsafe impl<const N: usize> Sync for TaskStack<N> {}
+
+    impl<const N: usize> fmt::Debug for TaskStack<N> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("TaskStack")
+                .field("region", &self.region.get())
+                .field("len", &N)
+                .field("fill_pattern", &self.fill_pattern)
+                .finish()
+        }
+    }
+
+    impl<const N: usize> TaskStack<N> {
+        /// Construct a `TaskStack`.
+        #[inline]
+        pub const fn new() -> Self {
+            Self {
+                region: UnsafeCell::new([MaybeUninit::uninit(); N]),
+                fill_pattern: None,
+                state: AtomicU8::new(STACK_UNUSED),
+            }
+        }
+
+        /// Construct a `TaskStack` that is filled with `pattern` when a task
+        /// is created with it. This enables [`Self::high_water_mark`].
+        #[inline]
+        pub const fn with_fill_pattern(pattern: u8) -> Self {
+            Self {
+                fill_pattern: Some(pattern),
+                ..Self::new()
+            }
+        }
+
+        /// Get the size of the stack region.
+        #[inline]
+        pub const fn len(&self) -> usize {
+            N
+        }
+
+        /// Get a flag indicating whether the stack region is empty.
+        #[inline]
+        pub const fn is_empty(&self) -> bool {
+            N == 0
+        }
+
+        /// Get the peak stack usage of the task in bytes.
+        ///
+        /// The stack is assumed to grow downward. The usage is estimated by
+        /// finding the lowest byte that doesn't match the fill pattern, so it
+        /// might be underestimated if the task has written bytes that happen
+        /// to match the pattern.
+        ///
+        /// Returns `None` if `self` doesn't have a fill pattern or hasn't been
+        /// used to create a task yet.
+        ///
+        /// # Safety
+        ///
+        /// No task may be running on the region during the call. That is, the
+        /// task created with `self` must be dormant or suspended, or it must
+        /// have been deleted and `self` not used to create another task since
+        /// then.
+        pub unsafe fn high_water_mark(&self) -> Option<usize> {
+            let pattern = self.fill_pattern?;
+            if self.state.load(Ordering::Acquire) != STACK_READY {
+                return None;
+            }
+
+            let start = self.region.get() as *const u8;
+            let untouched = (0..N)
+                // Safety: The region was initialized with the fill pattern,
+                //         and the caller guarantees that no task is writing to
+                //         it concurrently
+                .take_while(|&i| unsafe { *start.add(i) } == pattern)
+                .count();
+            Some(N - untouched)
+        }
+
+        fn as_region(&'static self) -> StackRegion {
+            StackRegion {
+                start: self.region.get() as *mut u8,
+                len: N,
+                fill_pattern: self.fill_pattern,
+                state: &self.state,
+            }
+        }
+    }
+
+    /// A type-erased reference to a [`TaskStack`].
+    #[derive(Clone, Copy)]
+    struct StackRegion {
+        start: *mut u8,
+        len: usize,
+        fill_pattern: Option<u8>,
+        state: &'static AtomicU8,
+    }
+
+    impl StackRegion {
+        /// Take the exclusive ownership of the region and fill it with the
+        /// fill pattern. Returns `false` if the region is already in use.
+        fn claim(self) -> bool {
+            if self
+                .state
+                .compare_exchange(
+                    STACK_UNUSED,
+                    STACK_CLAIMED,
+                    Ordering::Acquire,
+                    Ordering::Relaxed,
+                )
+                .is_err()
+            {
+                return false;
+            }
+
+            if let Some(pattern) = self.fill_pattern {
+                // Safety: We own the region now
+                unsafe { self.start.write_bytes(pattern, self.len) };
+            }
+            self.state.store(STACK_READY, Ordering::Release);
+            true
+        }
+    }
+
+    /// Return a region claimed by [`StackRegion::claim`].
+    fn release_stack(state: &AtomicU8) {
+        state.store(STACK_UNUSED, Ordering::Release);
+    }
+
+    impl<const N: usize> Default for TaskStack<N> {
+        #[inline]
+        fn default() -> Self {
+            Self::new()
+        }
+    }
+
     /// The builder type for [tasks](Task). Created by [`Task::build`].
     ///
     /// Its generic parameters are an implementation detail.
@@ -1586,6 +2186,8 @@ mod owned {
         stack: Stack,
         initial_priority: InitialPriority,
         assign_to_current_procesor: bool,
+        /// The region specified by [`Builder::stack`], claimed by `finish`
+        task_stack: Option<StackRegion>,
         #[cfg(not(feature = "none"))]
         raw: abi::T_CTSK,
     }
@@ -1633,6 +2235,7 @@ mod owned {
                 stack: builder_hole::__stack_is_not_specified__,
                 initial_priority: builder_hole::__initial_priority_is_not_specified__,
                 assign_to_current_procesor: true,
+                task_stack: None,
                 #[cfg(any(feature = "asp3", feature = "solid_asp3"))]
                 raw: abi::T_CTSK {
                     tskatr: abi::TA_NULL,
@@ -1672,6 +2275,7 @@ mod owned {
                 stack: self.stack,
                 initial_priority: self.initial_priority,
                 assign_to_current_procesor: self.assign_to_current_procesor,
+                task_stack: self.task_stack,
                 #[cfg(not(feature = "none"))]
                 raw: abi::T_CTSK {
                     task: Some(task),
@@ -1690,6 +2294,7 @@ mod owned {
                 stack: (),
                 initial_priority: self.initial_priority,
                 assign_to_current_procesor: self.assign_to_current_procesor,
+                task_stack: None,
                 #[cfg(not(feature = "none"))]
                 raw: abi::T_CTSK {
                     stksz: size,
@@ -1699,6 +2304,31 @@ mod owned {
             }
         }
 
+        /// (**Mandatory**) Specify to use the specified stack region.
+        ///
+        /// `stack` is claimed when the task is created. The creation fails
+        /// with `BadParam` if `stack` is in use by another task.
+        #[inline]
+        pub fn stack<const N: usize>(
+            self,
+            stack: &'static TaskStack<N>,
+        ) -> Builder<Start, (), InitialPriority> {
+            let region = stack.as_region();
+            Builder {
+                start: self.start,
+                stack: (),
+                initial_priority: self.initial_priority,
+                assign_to_current_procesor: self.assign_to_current_procesor,
+                task_stack: Some(region),
+                #[cfg(not(feature = "none"))]
+                raw: abi::T_CTSK {
+                    stksz: N,
+                    stk: region.start,
+                    ..self.raw
+                },
+            }
+        }
+
         /// (**Mandatory**) Specify the initial priority.
         #[inline]
         pub fn initial_priority(self, value: Priority) -> Builder<Start, Stack, ()> {
@@ -1707,6 +2337,7 @@ mod owned {
                 stack: self.stack,
                 initial_priority: (),
                 assign_to_current_procesor: self.assign_to_current_procesor,
+                task_stack: self.task_stack,
                 #[cfg(not(feature = "none"))]
                 raw: abi::T_CTSK {
                     itskpri: value,
@@ -1785,9 +2416,28 @@ mod owned {
             match () {
                 #[cfg(not(feature = "none"))]
                 () => unsafe {
-                    let id = Error::err_if_negative(abi::acre_tsk(&self.raw))?;
+                    if let Some(region) = self.task_stack {
+                        if !region.claim() {
+                            // Safety: `E_PAR` is handled by `BuildError`
+                            return Err(Error::new_unchecked(ErrorCode::new_unchecked(abi::E_PAR)));
+                        }
+                    }
+
+                    let id = match Error::err_if_negative(abi::acre_tsk(&self.raw)) {
+                        Ok(id) => id,
+                        Err(e) => {
+                            if let Some(region) = self.task_stack {
+                                release_stack(region.state);
+                            }
+                            return Err(e);
+                        }
+                    };
+
                     // Safety: We own the task we create
-                    Ok(Task::from_raw_nonnull(abi::NonNullID::new_unchecked(id)))
+                    Ok(Task {
+                        task: TaskRef::from_raw_nonnull(abi::NonNullID::new_unchecked(id)),
+                        stack_state: self.task_stack.map(|region| region.state),
+                    })
                 },
                 #[cfg(feature = "none")]
                 () => unimplemented!(),
@@ -1813,21 +2463,37 @@ mod owned {
     /// panic if the deletion fails.
     ///
     /// [Deletes]: TaskRef::delete
-    #[derive(PartialEq, Eq)]
     #[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
-    pub struct Task(TaskRef<'static>);
+    pub struct Task {
+        task: TaskRef<'static>,
+        /// The state of the [`TaskStack`] to release when the task is
+        /// deleted
+        stack_state: Option<&'static AtomicU8>,
+    }
+
+    impl PartialEq for Task {
+        #[inline]
+        fn eq(&self, other: &Self) -> bool {
+            self.task == other.task
+        }
+    }
+
+    impl Eq for Task {}
 
     impl fmt::Debug for Task {
         #[inline]
         fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-            self.0.fmt(f)
+            self.task.fmt(f)
         }
     }
 
     impl Drop for Task {
         #[inline]
         fn drop(&mut self) {
-            unsafe { self.0.delete().unwrap() };
+            unsafe { self.task.delete().unwrap() };
+            if let Some(state) = self.stack_state {
+                release_stack(state);
+            }
         }
     }
 
@@ -1839,13 +2505,16 @@ mod owned {
         /// See [Object ID Wrappers](crate#object-id-wrappers).
         #[inline]
         pub const unsafe fn from_raw_nonnull(id: abi::NonNullID) -> Self {
-            Self(unsafe { TaskRef::from_raw_nonnull(id) })
+            Self {
+                task: unsafe { TaskRef::from_raw_nonnull(id) },
+                stack_state: None,
+            }
         }
 
         /// Consume and "leak" `self`, returning a reference `TaskRef<'a>`.
         #[inline]
         pub const fn leak<'a>(self) -> TaskRef<'a> {
-            let out = self.0;
+            let out = self.task;
             core::mem::forget(self);
             out
         }
@@ -1853,13 +2522,13 @@ mod owned {
         /// Get the raw object ID.
         #[inline]
         pub const fn as_raw(&self) -> abi::ID {
-            self.0.as_raw()
+            self.task.as_raw()
         }
 
         /// Get the raw object ID as [`abi::NonNullID`].
         #[inline]
         pub const fn as_raw_nonnull(&self) -> abi::NonNullID {
-            self.0.as_raw_nonnull()
+            self.task.as_raw_nonnull()
         }
 
         /// Borrow `Task` as [`TaskRef`].
@@ -1868,7 +2537,7 @@ mod owned {
         /// are implemented on `TaskRef` but not `Task`.
         #[inline]
         pub const fn as_ref(&self) -> TaskRef<'_> {
-            self.0
+            self.task
         }
     }
 }
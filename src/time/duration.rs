@@ -104,6 +104,13 @@ impl TryFrom<StdDuration> for Duration {
     }
 }
 
+impl From<Duration> for StdDuration {
+    #[inline]
+    fn from(d: Duration) -> Self {
+        Self::from_micros(d.as_raw().into())
+    }
+}
+
 impl TryFrom<Timeout> for Duration {
     type Error = super::TryFromDurationError;
 
@@ -0,0 +1,99 @@
+use crate::abi;
+use core::{
+    ops::{Add, Sub},
+    time::Duration as StdDuration,
+};
+
+use super::Duration;
+
+/// A measurement of the high-resolution timer ([`abi::HRTCNT`]), measured in
+/// microseconds.
+///
+/// The high-resolution timer is monotonic and cheap to read, and it can be
+/// read from any context.
+///
+/// This crate assumes that the kernel is configured with a 64-bit counter
+/// (`USE_64BIT_HRTCNT`) that covers its entire value range (`TCYC_HRTCNT` is
+/// not defined), which takes more than 500,000 years to wrap around.
+/// Therefore, `Instant` is ordered, and the arithmetic operations don't
+/// account for wraparound.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct Instant {
+    value: abi::HRTCNT,
+}
+
+impl Instant {
+    /// Construct a new `Instant` from a raw value.
+    #[inline]
+    pub const fn from_raw(value: abi::HRTCNT) -> Self {
+        Self { value }
+    }
+
+    /// Get the raw value.
+    #[inline]
+    pub const fn as_raw(self) -> abi::HRTCNT {
+        self.value
+    }
+
+    /// `fch_hrt`: Get the current value of the high-resolution timer.
+    #[inline]
+    #[doc(alias = "fch_hrt")]
+    pub fn now() -> Self {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => Self::from_raw(unsafe { abi::fch_hrt() }),
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Get the amount of time elapsed from `earlier` to `self`. Returns zero
+    /// if `earlier` is later than `self`.
+    #[inline]
+    pub fn duration_since(self, earlier: Self) -> StdDuration {
+        StdDuration::from_micros(self.value.saturating_sub(earlier.value))
+    }
+
+    /// Get the amount of time elapsed from `earlier` to `self` as
+    /// [`Duration`]. Returns `None` if `earlier` is later than `self` or the
+    /// result is not representable by `Duration`.
+    #[inline]
+    pub fn checked_duration_since(self, earlier: Self) -> Option<Duration> {
+        Duration::from_micros(self.value.checked_sub(earlier.value)?)
+    }
+
+    /// Get the amount of time elapsed since `self` was created.
+    #[inline]
+    pub fn elapsed(self) -> StdDuration {
+        Self::now().duration_since(self)
+    }
+}
+
+impl Add<Duration> for Instant {
+    type Output = Self;
+
+    /// Get the `Instant` that is `rhs` later than `self`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the result overflows [`abi::HRTCNT`].
+    #[inline]
+    fn add(self, rhs: Duration) -> Self {
+        Self::from_raw(
+            self.value
+                .checked_add(rhs.as_raw().into())
+                .expect("overflow when adding a duration to an instant"),
+        )
+    }
+}
+
+impl Sub for Instant {
+    type Output = StdDuration;
+
+    /// Equivalent to [`Instant::duration_since`].
+    #[inline]
+    fn sub(self, rhs: Self) -> StdDuration {
+        self.duration_since(rhs)
+    }
+}
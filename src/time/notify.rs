@@ -0,0 +1,280 @@
+//! Notification methods of time event handlers
+use core::sync::atomic::AtomicIsize;
+
+use crate::{
+    abi,
+    closure::{Closure, IntoClosure},
+    dataqueue::{DataElement, DataqueueRef},
+    eventflag::{EventflagRef, Pattern},
+    semaphore::SemaphoreRef,
+    task::TaskRef,
+};
+
+/// The notification method of a time event handler (`T_NFYINFO`), i.e.,
+/// what the kernel does when a [cyclic handler] or an [alarm handler] is
+/// invoked.
+///
+/// # Example
+///
+/// ```rust,no_run
+/// use itron::{cyclic::Cyclic, eventflag::Eventflag, time::{Duration, Notify}};
+/// let eventflag = Eventflag::build()
+///     .finish()
+///     .expect("failed to create an eventflag")
+///     .leak();
+/// let cyclic = Cyclic::build()
+///     .notify(Notify::SetFlag(eventflag, 1))
+///     .period(Duration::from_millis(10).unwrap())
+///     .finish_and_start()
+///     .expect("failed to create a cyclic handler");
+///
+/// eventflag.wait(1, itron::eventflag::WaitMode::Any)
+///     .expect("failed to wait for the eventflag");
+/// ```
+///
+/// [cyclic handler]: crate::cyclic
+/// [alarm handler]: crate::alarm
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+pub enum Notify {
+    /// `TNFY_HANDLER`: Call the specified closure. Use [`Notify::handler`]
+    /// to construct this variant.
+    #[non_exhaustive]
+    #[doc(alias = "TNFY_HANDLER")]
+    Handler(Closure),
+    /// `TNFY_SETVAR`: Store the specified value to the specified variable.
+    #[doc(alias = "TNFY_SETVAR")]
+    SetVar(&'static AtomicIsize, isize),
+    /// `TNFY_INCVAR`: Increment the specified variable.
+    #[doc(alias = "TNFY_INCVAR")]
+    IncVar(&'static AtomicIsize),
+    /// `TNFY_ACTTSK`: Activate the specified task.
+    #[doc(alias = "TNFY_ACTTSK")]
+    ActivateTask(TaskRef<'static>),
+    /// `TNFY_WUPTSK`: Wake up the specified task.
+    #[doc(alias = "TNFY_WUPTSK")]
+    WakeTask(TaskRef<'static>),
+    /// `TNFY_SIGSEM`: Signal the specified semaphore.
+    #[doc(alias = "TNFY_SIGSEM")]
+    SignalSemaphore(SemaphoreRef<'static>),
+    /// `TNFY_SETFLG`: Set the specified bits of the specified eventflag.
+    #[doc(alias = "TNFY_SETFLG")]
+    SetFlag(EventflagRef<'static>, Pattern),
+    /// `TNFY_SNDDTQ`: Send the specified data element to the specified
+    /// dataqueue.
+    #[doc(alias = "TNFY_SNDDTQ")]
+    SendDataqueue(DataqueueRef<'static>, DataElement),
+}
+
+/// The error notification method of a time event handler, i.e., what the
+/// kernel does when it fails to perform the [`Notify`] action (e.g., because
+/// the target object was deleted or its queue is full).
+///
+/// Error notification can't be used with [`Notify::Handler`].
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "doc_cfg", doc(cfg(feature = "dcre")))]
+pub enum ErrorNotify {
+    /// `TENFY_SETVAR`: Store the error code to the specified variable.
+    #[doc(alias = "TENFY_SETVAR")]
+    SetVar(&'static AtomicIsize),
+    /// `TENFY_INCVAR`: Increment the specified variable.
+    #[doc(alias = "TENFY_INCVAR")]
+    IncVar(&'static AtomicIsize),
+    /// `TENFY_ACTTSK`: Activate the specified task.
+    #[doc(alias = "TENFY_ACTTSK")]
+    ActivateTask(TaskRef<'static>),
+    /// `TENFY_WUPTSK`: Wake up the specified task.
+    #[doc(alias = "TENFY_WUPTSK")]
+    WakeTask(TaskRef<'static>),
+    /// `TENFY_SIGSEM`: Signal the specified semaphore.
+    #[doc(alias = "TENFY_SIGSEM")]
+    SignalSemaphore(SemaphoreRef<'static>),
+    /// `TENFY_SETFLG`: Set the specified bits of the specified eventflag.
+    #[doc(alias = "TENFY_SETFLG")]
+    SetFlag(EventflagRef<'static>, Pattern),
+    /// `TENFY_SNDDTQ`: Send the error code to the specified dataqueue.
+    #[doc(alias = "TENFY_SNDDTQ")]
+    SendDataqueue(DataqueueRef<'static>),
+}
+
+/// The bits of `T_NFYINFO::nfymode` specifying the notification
+/// method. The rest specify the error notification method.
+#[cfg(not(feature = "none"))]
+const TNFY_MASK: abi::MODE = 0x0f;
+
+impl Notify {
+    /// Construct [`Notify::Handler`].
+    #[inline]
+    pub fn handler(value: impl IntoClosure + Send) -> Self {
+        Self::Handler(value.into_closure())
+    }
+
+    /// Overwrite the notification method in `raw`, preserving the error
+    /// notification method.
+    #[cfg(not(feature = "none"))]
+    pub(crate) fn write_to(self, raw: &mut abi::T_NFYINFO) {
+        let (mode, nfy) = match self {
+            Self::Handler((handler, exinf)) => (
+                abi::TNFY_HANDLER,
+                abi::T_NFY {
+                    handler: abi::T_NFY_HDR {
+                        exinf,
+                        tmehdr: Some(handler),
+                    },
+                },
+            ),
+            Self::SetVar(var, value) => (
+                abi::TNFY_SETVAR,
+                abi::T_NFY {
+                    setvar: abi::T_NFY_VAR {
+                        p_var: var_as_ptr(var),
+                        value,
+                    },
+                },
+            ),
+            Self::IncVar(var) => (
+                abi::TNFY_INCVAR,
+                abi::T_NFY {
+                    incvar: abi::T_NFY_IVAR {
+                        p_var: var_as_ptr(var),
+                    },
+                },
+            ),
+            Self::ActivateTask(task) => (
+                abi::TNFY_ACTTSK,
+                abi::T_NFY {
+                    acttsk: abi::T_NFY_TSK {
+                        tskid: task.as_raw(),
+                    },
+                },
+            ),
+            Self::WakeTask(task) => (
+                abi::TNFY_WUPTSK,
+                abi::T_NFY {
+                    wuptsk: abi::T_NFY_TSK {
+                        tskid: task.as_raw(),
+                    },
+                },
+            ),
+            Self::SignalSemaphore(semaphore) => (
+                abi::TNFY_SIGSEM,
+                abi::T_NFY {
+                    sigsem: abi::T_NFY_SEM {
+                        semid: semaphore.as_raw(),
+                    },
+                },
+            ),
+            Self::SetFlag(eventflag, pattern) => (
+                abi::TNFY_SETFLG,
+                abi::T_NFY {
+                    setflg: abi::T_NFY_FLG {
+                        flgid: eventflag.as_raw(),
+                        #[cfg(all(
+                            any(feature = "solid_asp3", feature = "solid_fmp3"),
+                            target_pointer_width = "64",
+                        ))]
+                        __pad_for_aarch64: 0,
+                        flgptn: pattern,
+                    },
+                },
+            ),
+            Self::SendDataqueue(dataqueue, data) => (
+                abi::TNFY_SNDDTQ,
+                abi::T_NFY {
+                    snddtq: abi::T_NFY_DTQ {
+                        dtqid: dataqueue.as_raw(),
+                        #[cfg(all(
+                            any(feature = "solid_asp3", feature = "solid_fmp3"),
+                            target_pointer_width = "64",
+                        ))]
+                        __pad_for_aarch64: 0,
+                        data: data as isize,
+                    },
+                },
+            ),
+        };
+        raw.nfymode = (raw.nfymode & !TNFY_MASK) | mode;
+        raw.nfy = nfy;
+    }
+}
+
+impl ErrorNotify {
+    /// Overwrite the error notification method in `raw`, preserving the
+    /// notification method.
+    #[cfg(not(feature = "none"))]
+    pub(crate) fn write_to(self, raw: &mut abi::T_NFYINFO) {
+        let (mode, enfy) = match self {
+            Self::SetVar(var) => (
+                abi::TENFY_SETVAR,
+                abi::T_ENFY {
+                    setvar: abi::T_ENFY_VAR {
+                        p_var: var_as_ptr(var),
+                    },
+                },
+            ),
+            Self::IncVar(var) => (
+                abi::TENFY_INCVAR,
+                abi::T_ENFY {
+                    incvar: abi::T_NFY_IVAR {
+                        p_var: var_as_ptr(var),
+                    },
+                },
+            ),
+            Self::ActivateTask(task) => (
+                abi::TENFY_ACTTSK,
+                abi::T_ENFY {
+                    acttsk: abi::T_NFY_TSK {
+                        tskid: task.as_raw(),
+                    },
+                },
+            ),
+            Self::WakeTask(task) => (
+                abi::TENFY_WUPTSK,
+                abi::T_ENFY {
+                    wuptsk: abi::T_NFY_TSK {
+                        tskid: task.as_raw(),
+                    },
+                },
+            ),
+            Self::SignalSemaphore(semaphore) => (
+                abi::TENFY_SIGSEM,
+                abi::T_ENFY {
+                    sigsem: abi::T_NFY_SEM {
+                        semid: semaphore.as_raw(),
+                    },
+                },
+            ),
+            Self::SetFlag(eventflag, pattern) => (
+                abi::TENFY_SETFLG,
+                abi::T_ENFY {
+                    setflg: abi::T_NFY_FLG {
+                        flgid: eventflag.as_raw(),
+                        #[cfg(all(
+                            any(feature = "solid_asp3", feature = "solid_fmp3"),
+                            target_pointer_width = "64",
+                        ))]
+                        __pad_for_aarch64: 0,
+                        flgptn: pattern,
+                    },
+                },
+            ),
+            Self::SendDataqueue(dataqueue) => (
+                abi::TENFY_SNDDTQ,
+                abi::T_ENFY {
+                    snddtq: abi::T_ENFY_DTQ {
+                        dtqid: dataqueue.as_raw(),
+                    },
+                },
+            ),
+        };
+        raw.nfymode = (raw.nfymode & TNFY_MASK) | mode;
+        raw.enfy = enfy;
+    }
+}
+
+#[cfg(not(feature = "none"))]
+#[inline]
+fn var_as_ptr(var: &'static AtomicIsize) -> *mut isize {
+    // `AtomicIsize` has the same in-memory representation as `isize`
+    var as *const AtomicIsize as *mut isize
+}
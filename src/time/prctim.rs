@@ -0,0 +1,96 @@
+//! Processor time
+use crate::abi;
+use core::{convert::TryFrom, time::Duration as StdDuration};
+
+/// A processor time value ([`abi::PRCTIM`]), measured in microseconds.
+///
+/// Processor time is the amount of time a task has actually been running.
+/// It's used to specify and inspect the budget of a task's [overrun timer].
+///
+/// [overrun timer]: crate::task::TaskRef::start_overrun_timer
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+#[cfg_attr(
+    feature = "doc_cfg",
+    doc(cfg(all(feature = "asp3", feature = "ovrhdr")))
+)]
+pub struct ProcessorTime {
+    value: abi::PRCTIM,
+}
+
+impl ProcessorTime {
+    /// Construct a new `ProcessorTime` from a raw value.
+    #[inline]
+    pub const fn from_raw(value: abi::PRCTIM) -> Self {
+        Self { value }
+    }
+
+    /// Get the raw value.
+    #[inline]
+    pub const fn as_raw(self) -> abi::PRCTIM {
+        self.value
+    }
+
+    /// Construct a new `ProcessorTime` from the specified number of seconds.
+    ///
+    /// Returns `None` if the specified value is not representable.
+    #[inline]
+    pub const fn from_secs(secs: u64) -> Option<Self> {
+        if secs > u64::MAX / 1_000_000 {
+            None
+        } else {
+            Self::from_micros(secs * 1_000_000)
+        }
+    }
+
+    /// Construct a new `ProcessorTime` from the specified number of
+    /// milliseconds.
+    ///
+    /// Returns `None` if the specified value is not representable.
+    #[inline]
+    pub const fn from_millis(millis: u64) -> Option<Self> {
+        if millis > u64::MAX / 1_000 {
+            None
+        } else {
+            Self::from_micros(millis * 1_000)
+        }
+    }
+
+    /// Construct a new `ProcessorTime` from the specified number of
+    /// microseconds.
+    ///
+    /// Returns `None` if the specified value is not representable.
+    #[inline]
+    pub const fn from_micros(micros: u64) -> Option<Self> {
+        if micros > abi::PRCTIM::MAX as u64 {
+            None
+        } else {
+            Some(Self::from_raw(micros as abi::PRCTIM))
+        }
+    }
+
+    /// Get the total number of whole microseconds.
+    #[inline]
+    pub const fn as_micros(self) -> u64 {
+        self.value as u64
+    }
+}
+
+impl From<ProcessorTime> for StdDuration {
+    #[inline]
+    fn from(x: ProcessorTime) -> Self {
+        Self::from_micros(x.as_micros())
+    }
+}
+
+impl TryFrom<StdDuration> for ProcessorTime {
+    type Error = super::TryFromDurationError;
+
+    #[inline]
+    fn try_from(d: StdDuration) -> Result<Self, Self::Error> {
+        u64::try_from(d.as_micros())
+            .ok()
+            .and_then(Self::from_micros)
+            .ok_or(super::TryFromDurationError(()))
+    }
+}
@@ -1,5 +1,190 @@
 //! System time management
-// TODO: set_tim
-// TODO: get_tim
-// TODO: adj_tim
-// TODO: fch_hrt
+use crate::{
+    abi,
+    error::{Error, ErrorCode, ErrorKind, Kind},
+};
+use core::mem::MaybeUninit;
+
+use super::Duration;
+
+define_error_kind! {
+    /// Error type for [`SystemTime::now`].
+    pub enum NowError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for NowError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`SystemTime::set`].
+    pub enum SetError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+    }
+}
+
+impl ErrorKind for SetError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+define_error_kind! {
+    /// Error type for [`SystemTime::adjust`].
+    pub enum AdjustError {
+        #[cfg(not(feature = "none"))]
+        BadContext,
+        #[cfg(any())]
+        AccessDenied,
+        /// The adjustment amount is out of range.
+        #[cfg(not(feature = "none"))]
+        BadParam,
+        /// The adjustment cannot be made right now, e.g., because a previous
+        /// adjustment is still in progress.
+        #[cfg(not(feature = "none"))]
+        BadState,
+    }
+}
+
+impl ErrorKind for AdjustError {
+    fn from_error_code(code: ErrorCode) -> Option<Self> {
+        match code.get() {
+            #[cfg(not(feature = "none"))]
+            abi::E_CTX => Some(Self::BadContext(Kind::from_error_code(code))),
+            #[cfg(any())]
+            abi::E_OACV => Some(Self::AccessDenied(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_PAR => Some(Self::BadParam(Kind::from_error_code(code))),
+            #[cfg(not(feature = "none"))]
+            abi::E_OBJ => Some(Self::BadState(Kind::from_error_code(code))),
+            _ => None,
+        }
+    }
+}
+
+/// A system time value ([`abi::SYSTIM`]), measured in microseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct SystemTime {
+    value: abi::SYSTIM,
+}
+
+impl SystemTime {
+    /// Construct a new `SystemTime` from a raw value.
+    #[inline]
+    pub const fn from_raw(value: abi::SYSTIM) -> Self {
+        Self { value }
+    }
+
+    /// Get the raw value.
+    #[inline]
+    pub const fn as_raw(self) -> abi::SYSTIM {
+        self.value
+    }
+
+    /// `get_tim`: Get the current system time.
+    #[inline]
+    #[doc(alias = "get_tim")]
+    pub fn now() -> Result<Self, Error<NowError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                let mut out = MaybeUninit::uninit();
+                Error::err_if_negative(abi::get_tim(out.as_mut_ptr()))?;
+                Ok(Self::from_raw(out.assume_init()))
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `set_tim`: Set the system time to `self`.
+    #[inline]
+    #[doc(alias = "set_tim")]
+    pub fn set(self) -> Result<(), Error<SetError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::set_tim(self.as_raw()))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// `adj_tim`: Advance (if `micros` is positive) or delay (if `micros` is
+    /// negative) the system time by the specified number of microseconds.
+    #[inline]
+    #[doc(alias = "adj_tim")]
+    pub fn adjust(micros: i32) -> Result<(), Error<AdjustError>> {
+        match () {
+            #[cfg(not(feature = "none"))]
+            () => unsafe {
+                Error::err_if_negative(abi::adj_tim(micros))?;
+                Ok(())
+            },
+            #[cfg(feature = "none")]
+            () => unimplemented!(),
+        }
+    }
+
+    /// Add `duration` to `self`, returning `None` if an overflow occurred.
+    #[inline]
+    pub const fn checked_add(self, duration: Duration) -> Option<Self> {
+        if let Some(value) = self.value.checked_add(duration.as_raw() as abi::SYSTIM) {
+            Some(Self::from_raw(value))
+        } else {
+            None
+        }
+    }
+
+    /// Subtract `duration` from `self`, returning `None` if an underflow
+    /// occurred.
+    #[inline]
+    pub const fn checked_sub(self, duration: Duration) -> Option<Self> {
+        if let Some(value) = self.value.checked_sub(duration.as_raw() as abi::SYSTIM) {
+            Some(Self::from_raw(value))
+        } else {
+            None
+        }
+    }
+
+    /// Subtract `duration` from `self`, saturating at zero.
+    #[inline]
+    pub const fn saturating_sub(self, duration: Duration) -> Self {
+        Self::from_raw(self.value.saturating_sub(duration.as_raw() as abi::SYSTIM))
+    }
+
+    /// Get the amount of time elapsed from `earlier` to `self`.
+    ///
+    /// Returns `None` if `earlier` is later than `self` or the result is not
+    /// representable by [`Duration`].
+    #[inline]
+    pub fn duration_since(self, earlier: Self) -> Option<Duration> {
+        let micros = self.value.checked_sub(earlier.value)?;
+        Duration::from_micros(micros)
+    }
+}